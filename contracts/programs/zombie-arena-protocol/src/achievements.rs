@@ -0,0 +1,354 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, FreezeAccount, Mint, MintTo, Token, TokenAccount},
+};
+
+use crate::config::{ProgramConfig, PAUSE_NFT, PAUSE_TOKEN};
+use crate::events::{AchievementCreated, AchievementUnlocked};
+use crate::nft::WeaponData;
+use crate::{mint_zap, seeds, ErrorCode, PlayerState, TokenData};
+
+pub const MAX_ACHIEVEMENT_NAME_LEN: usize = 32;
+
+#[derive(Accounts)]
+#[instruction(achievement_id: u32)]
+pub struct CreateAchievement<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        seeds = [seeds::CONFIG],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Achievement::INIT_SPACE,
+        seeds = [seeds::ACHIEVEMENT, &achievement_id.to_le_bytes()],
+        bump,
+    )]
+    pub achievement: Account<'info, Achievement>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_achievement(
+    ctx: Context<CreateAchievement>,
+    achievement_id: u32,
+    name: String,
+    criteria: AchievementCriteria,
+    zap_reward: u64,
+) -> Result<()> {
+    require!(
+        !name.is_empty() && name.len() <= MAX_ACHIEVEMENT_NAME_LEN,
+        ErrorCode::InvalidAchievementName
+    );
+    require!(
+        zap_reward == 0 || !criteria.is_weapon_criteria(),
+        ErrorCode::WeaponAchievementReward
+    );
+    
+    let achievement = &mut ctx.accounts.achievement;
+    achievement.id = achievement_id;
+    achievement.name = name;
+    achievement.criteria = criteria;
+    achievement.zap_reward = zap_reward;
+    achievement.badges_awarded = 0;
+    achievement.bump = ctx.bumps.achievement;
+    
+    emit!(AchievementCreated {
+        achievement: achievement_id,
+        name: achievement.name.clone(),
+        criteria: achievement.criteria.clone(),
+        zap_reward,
+    });
+    msg!("Achievement {} created: {}", achievement_id, achievement.name);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClaimAchievement<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+    
+    #[account(
+        seeds = [seeds::CONFIG],
+        bump = config.bump,
+        constraint = !config.is_paused(PAUSE_NFT) @ ErrorCode::ProgramPaused,
+    )]
+    pub config: Box<Account<'info, ProgramConfig>>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::ACHIEVEMENT, &achievement.id.to_le_bytes()],
+        bump = achievement.bump,
+    )]
+    pub achievement: Box<Account<'info, Achievement>>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::PLAYER_STATE, player.key().as_ref()],
+        bump = player_state.bump,
+    )]
+    pub player_state: Box<Account<'info, PlayerState>>,
+    
+    /// Weapon the criteria is checked against, for weapon achievements.
+    #[account(
+        seeds = [seeds::WEAPON, weapon_data.mint.as_ref()],
+        bump,
+    )]
+    pub weapon_data: Option<Box<Account<'info, WeaponData>>>,
+    
+    /// The player's token account holding `weapon_data`'s NFT.
+    pub weapon_account: Option<Box<Account<'info, TokenAccount>>>,
+    
+    #[account(
+        init,
+        payer = player,
+        space = 8 + AchievementBadge::INIT_SPACE,
+        seeds = [seeds::BADGE, achievement.key().as_ref(), player.key().as_ref()],
+        bump,
+    )]
+    pub badge: Box<Account<'info, AchievementBadge>>,
+    
+    #[account(
+        init,
+        payer = player,
+        mint::decimals = 0,
+        mint::authority = badge_authority,
+        mint::freeze_authority = badge_authority,
+        seeds = [seeds::BADGE_MINT, achievement.key().as_ref(), player.key().as_ref()],
+        bump,
+    )]
+    pub badge_mint: Box<Account<'info, Mint>>,
+    
+    #[account(
+        init,
+        payer = player,
+        associated_token::mint = badge_mint,
+        associated_token::authority = player,
+    )]
+    pub badge_account: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        seeds = [seeds::MINT_AUTHORITY],
+        bump,
+    )]
+    /// CHECK: PDA used as NFT mint and freeze authority
+    pub badge_authority: UncheckedAccount<'info>,
+    
+    // The $ZAP accounts are only needed when the achievement has a reward.
+    // Anchor can't tie optional accounts to each other, so
+    // `claim_achievement` checks them against `token_data.mint`.
+    #[account(
+        mut,
+        seeds = [seeds::TOKEN_DATA, token_data.mint.as_ref()],
+        bump = token_data.bump,
+    )]
+    pub token_data: Option<Box<Account<'info, TokenData>>>,
+    
+    #[account(mut)]
+    pub zap_mint: Option<Box<Account<'info, Mint>>>,
+    
+    /// CHECK: PDA used as $ZAP mint authority, checked in `claim_achievement`
+    pub zap_mint_authority: Option<UncheckedAccount<'info>>,
+    
+    #[account(mut)]
+    pub player_token_account: Option<Box<Account<'info, TokenAccount>>>,
+    
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Awards an achievement's badge, a frozen single-token mint that can't leave
+/// the player's wallet, and its $ZAP reward if it has one.
+pub fn claim_achievement(ctx: Context<ClaimAchievement>) -> Result<()> {
+    let achievement = &mut ctx.accounts.achievement;
+    let weapon_data = ctx.accounts.weapon_data.as_deref().map(|weapon| &**weapon);
+    if let Some(weapon) = weapon_data {
+        let weapon_account = ctx
+            .accounts
+            .weapon_account
+            .as_deref()
+            .ok_or(ErrorCode::NotWeaponHolder)?;
+        require!(
+            weapon.is_held_in(weapon_account, &ctx.accounts.player.key()),
+            ErrorCode::NotWeaponHolder
+        );
+    }
+    require!(
+        achievement.criteria.is_met(&ctx.accounts.player_state, weapon_data),
+        ErrorCode::AchievementNotEarned
+    );
+    
+    let seeds = &[seeds::MINT_AUTHORITY, &[ctx.bumps.badge_authority]];
+    let signer = &[&seeds[..]];
+    
+    let cpi_accounts = MintTo {
+        mint: ctx.accounts.badge_mint.to_account_info(),
+        to: ctx.accounts.badge_account.to_account_info(),
+        authority: ctx.accounts.badge_authority.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer,
+    );
+    token::mint_to(cpi_ctx, 1)?;
+    
+    let cpi_accounts = FreezeAccount {
+        account: ctx.accounts.badge_account.to_account_info(),
+        mint: ctx.accounts.badge_mint.to_account_info(),
+        authority: ctx.accounts.badge_authority.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer,
+    );
+    token::freeze_account(cpi_ctx)?;
+    
+    // A weapon can unlock its achievement for every wallet it passes
+    // through, so weapon achievements only ever award the badge.
+    let now = Clock::get()?.unix_timestamp;
+    let reward = if achievement.criteria.is_weapon_criteria() {
+        0
+    } else {
+        achievement.zap_reward
+    };
+    if reward > 0 {
+        require!(
+            !ctx.accounts.config.is_paused(PAUSE_TOKEN),
+            ErrorCode::ProgramPaused
+        );
+        let (
+            Some(token_data),
+            Some(zap_mint),
+            Some(zap_mint_authority),
+            Some(player_token_account),
+        ) = (
+            ctx.accounts.token_data.as_deref_mut(),
+            ctx.accounts.zap_mint.as_deref(),
+            ctx.accounts.zap_mint_authority.as_ref(),
+            ctx.accounts.player_token_account.as_deref(),
+        )
+        else {
+            return err!(ErrorCode::InvalidRewardAccounts);
+        };
+        let (mint_authority, mint_authority_bump) = Pubkey::find_program_address(
+            &[seeds::MINT_AUTHORITY, token_data.mint.as_ref()],
+            ctx.program_id,
+        );
+        require!(
+            zap_mint.key() == token_data.mint
+                && zap_mint_authority.key() == mint_authority
+                && player_token_account.owner == ctx.accounts.player.key()
+                && player_token_account.mint == token_data.mint,
+            ErrorCode::InvalidRewardAccounts
+        );
+        
+        token_data.record_mint(reward, now)?;
+        
+        let player_state = &mut ctx.accounts.player_state;
+        player_state.record_daily_earnings(reward, token_data.emission.player_daily_cap, now)?;
+        player_state.tokens_earned += reward;
+        
+        mint_zap(
+            &ctx.accounts.token_program,
+            zap_mint,
+            player_token_account,
+            zap_mint_authority,
+            mint_authority_bump,
+            reward,
+        )?;
+    }
+    
+    achievement.badges_awarded += 1;
+    
+    let badge = &mut ctx.accounts.badge;
+    badge.achievement = achievement.id;
+    badge.player = ctx.accounts.player.key();
+    badge.mint = ctx.accounts.badge_mint.key();
+    badge.awarded_at = now;
+    badge.bump = ctx.bumps.badge;
+    
+    emit!(AchievementUnlocked {
+        achievement: achievement.id,
+        player: badge.player,
+        badge_mint: badge.mint,
+        zap_reward: reward,
+    });
+    msg!(
+        "Achievement {} ({}) unlocked by {}, {} $ZAP",
+        achievement.id,
+        achievement.name,
+        badge.player,
+        reward
+    );
+    Ok(())
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Achievement {
+    pub id: u32,
+    #[max_len(MAX_ACHIEVEMENT_NAME_LEN)]
+    pub name: String,
+    pub criteria: AchievementCriteria,
+    /// $ZAP minted alongside the badge, zero for a badge only. Counts
+    /// toward the player's daily cap. Always zero for weapon achievements.
+    pub zap_reward: u64,
+    pub badges_awarded: u32,
+    pub bump: u8,
+}
+
+/// Proof a player holds an achievement, one per achievement and player.
+#[account]
+#[derive(InitSpace)]
+pub struct AchievementBadge {
+    pub achievement: u32,
+    pub player: Pubkey,
+    /// Frozen badge token minted to the player.
+    pub mint: Pubkey,
+    pub awarded_at: i64,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, InitSpace)]
+pub enum AchievementCriteria {
+    /// Survive to this round in a single match.
+    ReachRound(u32),
+    TotalKills(u64),
+    TotalHeadshots(u64),
+    TotalRevives(u64),
+    MatchesPlayed(u64),
+    /// Own a Pack-a-Punched wonder weapon.
+    PackAPunchWonderWeapon,
+}
+
+impl AchievementCriteria {
+    /// Criteria met by a weapon rather than the player's own stats.
+    pub fn is_weapon_criteria(&self) -> bool {
+        matches!(self, AchievementCriteria::PackAPunchWonderWeapon)
+    }
+    
+    pub fn is_met(&self, player_state: &PlayerState, weapon: Option<&WeaponData>) -> bool {
+        match self {
+            AchievementCriteria::ReachRound(round) => player_state.highest_round >= *round,
+            AchievementCriteria::TotalKills(kills) => player_state.total_kills >= *kills,
+            AchievementCriteria::TotalHeadshots(headshots) => {
+                player_state.total_headshots >= *headshots
+            }
+            AchievementCriteria::TotalRevives(revives) => player_state.total_revives >= *revives,
+            AchievementCriteria::MatchesPlayed(matches) => player_state.matches_played >= *matches,
+            AchievementCriteria::PackAPunchWonderWeapon => weapon.is_some_and(|weapon| {
+                weapon.is_pack_a_punched && weapon.weapon_type.is_wonder_weapon()
+            }),
+        }
+    }
+}
@@ -0,0 +1,228 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    ed25519_program,
+    sysvar::instructions::{load_current_index_checked, load_instruction_at_checked},
+};
+
+use crate::ErrorCode;
+
+// Layout of the ed25519 program's instruction data: a one-byte signature
+// count, one byte of padding, then one 14-byte offsets record per signature.
+const SIGNATURE_COUNT_OFFSET: usize = 0;
+const OFFSETS_START: usize = 2;
+const OFFSETS_LEN: usize = 14;
+const PUBKEY_LEN: usize = 32;
+
+/// Instruction index the ed25519 program uses to mean "data lives in this
+/// same instruction".
+const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+/// Checks that the instruction immediately before the current one is an
+/// ed25519 signature check of `message`, and returns the key that signed it.
+///
+/// The runtime has already rejected the transaction if that signature was
+/// invalid, so all that's left is making sure it covers the bytes we expect.
+/// Callers decide whether the returned signer is trusted.
+pub fn verify_ed25519_attestation(instructions: &AccountInfo, message: &[u8]) -> Result<Pubkey> {
+    let current_index = load_current_index_checked(instructions)? as usize;
+    require!(current_index > 0, ErrorCode::MissingAttestation);
+    
+    let ix = load_instruction_at_checked(current_index - 1, instructions)?;
+    require_keys_eq!(ix.program_id, ed25519_program::ID, ErrorCode::MissingAttestation);
+    
+    let data = &ix.data;
+    require!(
+        data.len() >= OFFSETS_START + OFFSETS_LEN && data[SIGNATURE_COUNT_OFFSET] == 1,
+        ErrorCode::InvalidAttestation
+    );
+    
+    let read_u16 = |at: usize| {
+        u16::from_le_bytes([data[OFFSETS_START + at], data[OFFSETS_START + at + 1]])
+    };
+    let signature_ix = read_u16(2);
+    let pubkey_offset = read_u16(4) as usize;
+    let pubkey_ix = read_u16(6);
+    let message_offset = read_u16(8) as usize;
+    let message_len = read_u16(10) as usize;
+    let message_ix = read_u16(12);
+    
+    // Everything has to be embedded in the ed25519 instruction itself,
+    // otherwise the signature could be checked against some other data.
+    require!(
+        signature_ix == CURRENT_INSTRUCTION
+            && pubkey_ix == CURRENT_INSTRUCTION
+            && message_ix == CURRENT_INSTRUCTION,
+        ErrorCode::InvalidAttestation
+    );
+    
+    let signed_message = data
+        .get(message_offset..message_offset + message_len)
+        .ok_or(ErrorCode::InvalidAttestation)?;
+    require!(signed_message == message, ErrorCode::InvalidAttestation);
+    
+    let signer = data
+        .get(pubkey_offset..pubkey_offset + PUBKEY_LEN)
+        .and_then(|bytes| Pubkey::try_from(bytes).ok())
+        .ok_or(ErrorCode::InvalidAttestation)?;
+    
+    Ok(signer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::solana_program::{
+        instruction::Instruction,
+        message::{Message, SanitizedMessage},
+        sysvar::{
+            self,
+            instructions::{construct_instructions_data, store_current_index},
+        },
+    };
+    
+    const MESSAGE: &[u8] = b"zap:test_message";
+    
+    // Where `ed25519_data` puts each part, right after the offsets record.
+    const PUBKEY_AT: usize = OFFSETS_START + OFFSETS_LEN;
+    const SIGNATURE_AT: usize = PUBKEY_AT + PUBKEY_LEN;
+    const MESSAGE_AT: usize = SIGNATURE_AT + 64;
+    
+    /// ed25519 program data for a single signature by `signer` over
+    /// `message`, everything embedded in the instruction itself. The
+    /// signature is zeroes: the runtime checks it, not the program.
+    fn ed25519_data(signer: &Pubkey, message: &[u8]) -> Vec<u8> {
+        let mut data = vec![1, 0];
+        for offset in [
+            SIGNATURE_AT as u16,
+            CURRENT_INSTRUCTION,
+            PUBKEY_AT as u16,
+            CURRENT_INSTRUCTION,
+            MESSAGE_AT as u16,
+            message.len() as u16,
+            CURRENT_INSTRUCTION,
+        ] {
+            data.extend_from_slice(&offset.to_le_bytes());
+        }
+        data.extend_from_slice(signer.as_ref());
+        data.extend_from_slice(&[0; 64]);
+        data.extend_from_slice(message);
+        data
+    }
+    
+    fn set_offset(data: &mut [u8], at: usize, value: u16) {
+        data[OFFSETS_START + at..OFFSETS_START + at + 2].copy_from_slice(&value.to_le_bytes());
+    }
+    
+    fn ed25519_ix(data: Vec<u8>) -> Instruction {
+        Instruction::new_with_bytes(ed25519_program::ID, &data, vec![])
+    }
+    
+    fn current_ix() -> Instruction {
+        Instruction::new_with_bytes(crate::ID, &[], vec![])
+    }
+    
+    /// Runs `verify_ed25519_attestation` as instruction `current` of a
+    /// transaction made of `instructions`.
+    fn verify(instructions: &[Instruction], current: u16, message: &[u8]) -> Result<Pubkey> {
+        let message_data = SanitizedMessage::try_from(Message::new(
+            instructions,
+            Some(&Pubkey::new_unique()),
+        ))
+        .unwrap();
+        let mut data = construct_instructions_data(&message_data.decompile_instructions());
+        store_current_index(&mut data, current);
+        
+        let key = sysvar::instructions::ID;
+        let owner = sysvar::ID;
+        let mut lamports = 0;
+        let info = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &owner, false, 0);
+        verify_ed25519_attestation(&info, message)
+    }
+    
+    fn verify_data(data: Vec<u8>) -> Result<Pubkey> {
+        verify(&[ed25519_ix(data), current_ix()], 1, MESSAGE)
+    }
+    
+    fn invalid() -> Error {
+        ErrorCode::InvalidAttestation.into()
+    }
+    
+    #[test]
+    fn accepts_an_attestation_of_the_message() {
+        let signer = Pubkey::new_unique();
+        assert_eq!(verify_data(ed25519_data(&signer, MESSAGE)).unwrap(), signer);
+    }
+    
+    #[test]
+    fn returns_whoever_signed() {
+        // Trusting the signer is up to the caller, so a wrong key still
+        // comes back, as itself.
+        let expected = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+        let signer = verify_data(ed25519_data(&other, MESSAGE)).unwrap();
+        assert_eq!(signer, other);
+        assert_ne!(signer, expected);
+    }
+    
+    #[test]
+    fn rejects_a_missing_attestation() {
+        let data = ed25519_data(&Pubkey::new_unique(), MESSAGE);
+        assert_eq!(
+            verify(&[current_ix(), ed25519_ix(data.clone())], 0, MESSAGE).unwrap_err(),
+            ErrorCode::MissingAttestation.into()
+        );
+        
+        let not_ed25519 = Instruction::new_with_bytes(Pubkey::new_unique(), &data, vec![]);
+        assert_eq!(
+            verify(&[not_ed25519, current_ix()], 1, MESSAGE).unwrap_err(),
+            ErrorCode::MissingAttestation.into()
+        );
+    }
+    
+    #[test]
+    fn rejects_anything_but_one_signature() {
+        for count in [0, 2] {
+            let mut data = ed25519_data(&Pubkey::new_unique(), MESSAGE);
+            data[SIGNATURE_COUNT_OFFSET] = count;
+            assert_eq!(verify_data(data).unwrap_err(), invalid());
+        }
+    }
+    
+    #[test]
+    fn rejects_data_from_other_instructions() {
+        // Signature, public key and message instruction indexes in turn.
+        for at in [2, 6, 12] {
+            let mut data = ed25519_data(&Pubkey::new_unique(), MESSAGE);
+            set_offset(&mut data, at, 0);
+            assert_eq!(verify_data(data).unwrap_err(), invalid());
+        }
+    }
+    
+    #[test]
+    fn rejects_a_different_message() {
+        let data = ed25519_data(&Pubkey::new_unique(), b"zap:other_message");
+        assert_eq!(verify_data(data).unwrap_err(), invalid());
+        
+        // A prefix of the expected message isn't enough either.
+        let data = ed25519_data(&Pubkey::new_unique(), &MESSAGE[..MESSAGE.len() - 1]);
+        assert_eq!(verify_data(data).unwrap_err(), invalid());
+    }
+    
+    #[test]
+    fn rejects_truncated_data() {
+        let data = ed25519_data(&Pubkey::new_unique(), MESSAGE);
+        
+        // Cut inside the offsets record.
+        assert_eq!(verify_data(data[..PUBKEY_AT - 1].to_vec()).unwrap_err(), invalid());
+        // Cut inside the message.
+        assert_eq!(verify_data(data[..data.len() - 1].to_vec()).unwrap_err(), invalid());
+        
+        // Offsets pointing past the end of the data.
+        let mut past_end = data.clone();
+        set_offset(&mut past_end, 4, (data.len() - PUBKEY_LEN + 1) as u16);
+        assert_eq!(verify_data(past_end).unwrap_err(), invalid());
+        let mut past_end = data.clone();
+        set_offset(&mut past_end, 8, u16::MAX);
+        assert_eq!(verify_data(past_end).unwrap_err(), invalid());
+    }
+}
@@ -0,0 +1,271 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::config::{ProgramConfig, PAUSE_TOKEN};
+use crate::events::{ChallengeCompleted, ChallengeCreated};
+use crate::{mint_zap, seeds, EarnReason, ErrorCode, PlayerState, TokenData, SECONDS_PER_DAY};
+
+#[derive(Accounts)]
+#[instruction(challenge_id: u32)]
+pub struct CreateChallenge<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        seeds = [seeds::CONFIG],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Challenge::INIT_SPACE,
+        seeds = [seeds::CHALLENGE, &challenge_id.to_le_bytes()],
+        bump,
+    )]
+    pub challenge: Account<'info, Challenge>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn create_challenge(
+    ctx: Context<CreateChallenge>,
+    challenge_id: u32,
+    objective: ChallengeObjective,
+    target: u64,
+    reward: u64,
+    window: ChallengeWindow,
+    start_time: i64,
+    end_time: i64,
+) -> Result<()> {
+    require!(target > 0, ErrorCode::InvalidChallenge);
+    require!(end_time > start_time, ErrorCode::InvalidChallenge);
+    
+    let challenge = &mut ctx.accounts.challenge;
+    challenge.id = challenge_id;
+    challenge.objective = objective;
+    challenge.target = target;
+    challenge.reward = reward;
+    challenge.window = window;
+    challenge.start_time = start_time;
+    challenge.end_time = end_time;
+    challenge.completions = 0;
+    challenge.bump = ctx.bumps.challenge;
+    
+    emit!(ChallengeCreated {
+        challenge: challenge_id,
+        objective: challenge.objective.clone(),
+        target,
+        reward,
+        window,
+        start_time,
+        end_time,
+    });
+    msg!(
+        "Challenge {} created: {:?} {} for {} $ZAP",
+        challenge_id,
+        challenge.objective,
+        target,
+        reward
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(window_index: u64)]
+pub struct CompleteChallenge<'info> {
+    #[account(
+        mut,
+        constraint = config.is_game_server(&game_server.key()) @ ErrorCode::Unauthorized,
+    )]
+    pub game_server: Signer<'info>,
+    
+    #[account(
+        seeds = [seeds::CONFIG],
+        bump = config.bump,
+        constraint = !config.is_paused(PAUSE_TOKEN) @ ErrorCode::ProgramPaused,
+    )]
+    pub config: Box<Account<'info, ProgramConfig>>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::CHALLENGE, &challenge.id.to_le_bytes()],
+        bump = challenge.bump,
+    )]
+    pub challenge: Box<Account<'info, Challenge>>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::PLAYER_STATE, player_state.player.as_ref()],
+        bump = player_state.bump,
+    )]
+    pub player_state: Box<Account<'info, PlayerState>>,
+    
+    // One completion record per player per window is what stops a reward
+    // from being paid twice.
+    #[account(
+        init,
+        payer = game_server,
+        space = 8 + ChallengeCompletion::INIT_SPACE,
+        seeds = [
+            seeds::CHALLENGE_COMPLETION,
+            challenge.key().as_ref(),
+            player_state.player.as_ref(),
+            &window_index.to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub completion: Box<Account<'info, ChallengeCompletion>>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::TOKEN_DATA, mint.key().as_ref()],
+        bump = token_data.bump,
+    )]
+    pub token_data: Box<Account<'info, TokenData>>,
+    
+    #[account(mut)]
+    pub mint: Box<Account<'info, Mint>>,
+    
+    #[account(
+        seeds = [seeds::MINT_AUTHORITY, mint.key().as_ref()],
+        bump,
+    )]
+    /// CHECK: PDA used as mint authority
+    pub mint_authority: AccountInfo<'info>,
+    
+    #[account(
+        mut,
+        constraint = player_token_account.owner == player_state.player,
+        constraint = player_token_account.mint == mint.key(),
+    )]
+    pub player_token_account: Box<Account<'info, TokenAccount>>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Pays a challenge's reward once the game server reports the player has met
+/// its objective in the current window.
+pub fn complete_challenge(ctx: Context<CompleteChallenge>, window_index: u64) -> Result<()> {
+    let challenge = &mut ctx.accounts.challenge;
+    
+    let now = Clock::get()?.unix_timestamp;
+    let current_window = challenge
+        .window_at(now)
+        .ok_or(ErrorCode::ChallengeNotActive)?;
+    require!(window_index == current_window, ErrorCode::ChallengeNotActive);
+    
+    let config = &ctx.accounts.config;
+    let reward = challenge.reward;
+    require!(
+        reward <= config.earn_caps[EarnReason::Challenge.index()],
+        ErrorCode::EarnCapExceeded
+    );
+    
+    let token_data = &mut ctx.accounts.token_data;
+    token_data.record_mint(reward, now)?;
+    
+    let player_state = &mut ctx.accounts.player_state;
+    player_state.record_daily_earnings(reward, token_data.emission.player_daily_cap, now)?;
+    player_state.tokens_earned += reward;
+    
+    mint_zap(
+        &ctx.accounts.token_program,
+        &ctx.accounts.mint,
+        &ctx.accounts.player_token_account,
+        &ctx.accounts.mint_authority,
+        ctx.bumps.mint_authority,
+        reward,
+    )?;
+    
+    challenge.completions += 1;
+    
+    let completion = &mut ctx.accounts.completion;
+    completion.challenge = challenge.id;
+    completion.player = player_state.player;
+    completion.window_index = window_index;
+    completion.reward = reward;
+    completion.completed_at = now;
+    completion.bump = ctx.bumps.completion;
+    
+    emit!(ChallengeCompleted {
+        challenge: challenge.id,
+        player: completion.player,
+        window_index,
+        reward,
+    });
+    msg!(
+        "Challenge {} window {} completed by {}: {} $ZAP",
+        challenge.id,
+        window_index,
+        completion.player,
+        reward
+    );
+    Ok(())
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Challenge {
+    pub id: u32,
+    pub objective: ChallengeObjective,
+    /// Amount of `objective` a player has to reach within one window.
+    pub target: u64,
+    /// $ZAP paid per completion, subject to the `Challenge` earn cap.
+    pub reward: u64,
+    pub window: ChallengeWindow,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub completions: u64,
+    pub bump: u8,
+}
+
+impl Challenge {
+    /// Index of the window containing `now`, counted from `start_time`, or
+    /// `None` outside the challenge's lifetime.
+    pub fn window_at(&self, now: i64) -> Option<u64> {
+        if now < self.start_time || now >= self.end_time {
+            return None;
+        }
+        Some(((now - self.start_time) / self.window.duration()) as u64)
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct ChallengeCompletion {
+    pub challenge: u32,
+    pub player: Pubkey,
+    pub window_index: u64,
+    pub reward: u64,
+    pub completed_at: i64,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, InitSpace)]
+pub enum ChallengeObjective {
+    KillZombies,
+    Headshots,
+    Revives,
+    ReachRound,
+    PlayMatches,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, InitSpace)]
+pub enum ChallengeWindow {
+    Daily,
+    Weekly,
+}
+
+impl ChallengeWindow {
+    pub fn duration(&self) -> i64 {
+        match self {
+            ChallengeWindow::Daily => SECONDS_PER_DAY,
+            ChallengeWindow::Weekly => 7 * SECONDS_PER_DAY,
+        }
+    }
+}
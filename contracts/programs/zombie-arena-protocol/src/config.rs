@@ -0,0 +1,255 @@
+use anchor_lang::prelude::*;
+
+use crate::events::*;
+use crate::program::ZombieArenaProtocol;
+use crate::{seeds, EarnReason, ErrorCode};
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + ProgramConfig::INIT_SPACE,
+        seeds = [seeds::CONFIG],
+        bump,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        constraint = program.programdata_address()? == Some(program_data.key()),
+    )]
+    pub program: Program<'info, ZombieArenaProtocol>,
+    
+    // Only the upgrade authority may create the config, so nobody can race
+    // the deployer to become admin.
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(admin.key()) @ ErrorCode::Unauthorized,
+    )]
+    pub program_data: Account<'info, ProgramData>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_config(
+    ctx: Context<InitializeConfig>,
+    game_server: Pubkey,
+    reward_oracle: Pubkey,
+    fee_recipient: Pubkey,
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    config.admin = ctx.accounts.admin.key();
+    config.pending_admin = None;
+    config.game_servers = vec![game_server];
+    config.reward_oracle = reward_oracle;
+    config.fee_recipient = fee_recipient;
+    config.features = FEATURE_EARN_VOUCHERS | FEATURE_SCORE_ATTESTATIONS;
+    config.paused = 0;
+    config.current_season = 0;
+    config.earn_caps = [0; EarnReason::COUNT];
+    config.bump = ctx.bumps.config;
+    
+    emit!(ConfigInitialized {
+        admin: config.admin,
+        game_server,
+        reward_oracle,
+        fee_recipient,
+    });
+    msg!("Program config initialized, game server: {}", game_server);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    pub admin: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::CONFIG],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+}
+
+pub fn propose_admin(ctx: Context<UpdateConfig>, new_admin: Pubkey) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    config.pending_admin = Some(new_admin);
+    
+    emit!(AdminProposed {
+        admin: config.admin,
+        pending_admin: new_admin,
+    });
+    msg!("Proposed {} as new admin", new_admin);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    pub new_admin: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::CONFIG],
+        bump = config.bump,
+        constraint = config.pending_admin == Some(new_admin.key()) @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+}
+
+pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    config.admin = ctx.accounts.new_admin.key();
+    config.pending_admin = None;
+    
+    emit!(AdminAccepted { admin: config.admin });
+    msg!("Admin handed over to {}", config.admin);
+    Ok(())
+}
+
+pub fn add_game_server(ctx: Context<UpdateConfig>, game_server: Pubkey) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    require!(
+        !config.is_game_server(&game_server),
+        ErrorCode::GameServerAlreadyRegistered
+    );
+    require!(
+        config.game_servers.len() < MAX_GAME_SERVERS,
+        ErrorCode::TooManyGameServers
+    );
+    config.game_servers.push(game_server);
+    
+    emit!(GameServerAdded { game_server });
+    msg!("Game server {} added", game_server);
+    Ok(())
+}
+
+pub fn remove_game_server(ctx: Context<UpdateConfig>, game_server: Pubkey) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    require!(
+        config.is_game_server(&game_server),
+        ErrorCode::GameServerNotRegistered
+    );
+    config.game_servers.retain(|key| *key != game_server);
+    
+    emit!(GameServerRemoved { game_server });
+    msg!("Game server {} removed", game_server);
+    Ok(())
+}
+
+pub fn set_reward_oracle(ctx: Context<UpdateConfig>, reward_oracle: Pubkey) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    config.reward_oracle = reward_oracle;
+    
+    emit!(RewardOracleSet { reward_oracle });
+    msg!("Reward oracle set to {}", reward_oracle);
+    Ok(())
+}
+
+pub fn set_fee_recipient(ctx: Context<UpdateConfig>, fee_recipient: Pubkey) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    config.fee_recipient = fee_recipient;
+    
+    emit!(FeeRecipientSet { fee_recipient });
+    msg!("Fee recipient set to {}", fee_recipient);
+    Ok(())
+}
+
+pub fn set_feature(ctx: Context<UpdateConfig>, feature: u32, enabled: bool) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    if enabled {
+        config.features |= feature;
+    } else {
+        config.features &= !feature;
+    }
+    
+    emit!(FeaturesSet {
+        features: config.features,
+    });
+    msg!("Feature flags set to {:#b}", config.features);
+    Ok(())
+}
+
+pub fn set_paused(ctx: Context<UpdateConfig>, paused: u8) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    config.paused = paused;
+    
+    emit!(PausedSet { paused });
+    msg!("Paused subsystems set to {:#b}", paused);
+    Ok(())
+}
+
+pub fn set_earn_cap(ctx: Context<UpdateConfig>, reason: EarnReason, cap: u64) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    config.earn_caps[reason.index()] = cap;
+    
+    emit!(EarnCapSet {
+        reason: reason.clone(),
+        cap,
+    });
+    msg!("Earn cap for {:?} set to {} $ZAP", reason, cap);
+    Ok(())
+}
+
+pub const MAX_GAME_SERVERS: usize = 8;
+
+/// `earn_tokens` accepts ed25519 vouchers from the reward oracle, not only
+/// its co-signature.
+pub const FEATURE_EARN_VOUCHERS: u32 = 1 << 0;
+/// `update_tournament_score` accepts ed25519 attestations from a game server,
+/// not only its signature.
+pub const FEATURE_SCORE_ATTESTATIONS: u32 = 1 << 1;
+
+// Pause flags, one per subsystem. Payouts and refunds (`distribute_prizes`,
+// `claim_refund`, `claim_season_reward`) and the transitions that unlock them
+// (`end_tournament`, `cancel_tournament`) stay open while paused so funds
+// never get stuck. For the same reason `PAUSE_NFT` stops new weapon mint
+// requests and mystery boxes but not `mint_weapon_nft`, which reveals
+// requests that were already paid for and would otherwise expire. Anything
+// that mints $ZAP also honours `PAUSE_TOKEN`, whichever subsystem it is in.
+pub const PAUSE_TOKEN: u8 = 1 << 0;
+pub const PAUSE_NFT: u8 = 1 << 1;
+pub const PAUSE_TOURNAMENT: u8 = 1 << 2;
+pub const PAUSE_GUILD: u8 = 1 << 3;
+pub const PAUSE_ALL: u8 = PAUSE_TOKEN | PAUSE_NFT | PAUSE_TOURNAMENT | PAUSE_GUILD;
+
+#[account]
+#[derive(InitSpace)]
+pub struct ProgramConfig {
+    pub admin: Pubkey,
+    /// Admin proposed by `propose_admin`, waiting to `accept_admin`.
+    pub pending_admin: Option<Pubkey>,
+    /// Keys the game backend uses to submit or attest to match results.
+    #[max_len(MAX_GAME_SERVERS)]
+    pub game_servers: Vec<Pubkey>,
+    /// Key that co-signs or issues vouchers for `earn_tokens` payouts.
+    pub reward_oracle: Pubkey,
+    /// Owner of the token accounts that collect protocol fees.
+    pub fee_recipient: Pubkey,
+    /// Bitmask of `FEATURE_*` toggles.
+    pub features: u32,
+    /// Bitmask of paused subsystems, see `PAUSE_*`.
+    pub paused: u8,
+    /// Season most recently opened by `start_season`.
+    pub current_season: u32,
+    /// Largest single `earn_tokens` payout per `EarnReason`, indexed by
+    /// `EarnReason::index`. Zero disables earning for that reason.
+    pub earn_caps: [u64; EarnReason::COUNT],
+    pub bump: u8,
+}
+
+impl ProgramConfig {
+    pub fn is_game_server(&self, key: &Pubkey) -> bool {
+        self.game_servers.contains(key)
+    }
+    
+    pub fn is_enabled(&self, feature: u32) -> bool {
+        self.features & feature == feature
+    }
+    
+    pub fn is_paused(&self, subsystem: u8) -> bool {
+        self.paused & subsystem != 0
+    }
+}
@@ -0,0 +1,375 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+use crate::config::ProgramConfig;
+use crate::events::DropTableSet;
+use crate::nft::{WeaponRarity, WeaponType};
+use crate::supply::WeaponSupply;
+use crate::{seeds, ErrorCode};
+
+pub const MAX_DROP_ENTRIES: usize = 32;
+
+/// Domain tag hashed into every weapon roll.
+pub const WEAPON_ROLL_TAG: &[u8] = b"zap:weapon_roll";
+
+// Layout of the SlotHashes sysvar: a u64 entry count, then (slot, hash)
+// pairs with the most recent slot first.
+const SLOT_HASHES_LEN_SIZE: usize = 8;
+const SLOT_HASH_ENTRY_SIZE: usize = 8 + 32;
+
+#[derive(Accounts)]
+pub struct InitializeDropTable<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        seeds = [seeds::CONFIG],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + DropTable::INIT_SPACE,
+        seeds = [seeds::DROP_TABLE],
+        bump,
+    )]
+    pub drop_table: Account<'info, DropTable>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_drop_table(
+    ctx: Context<InitializeDropTable>,
+    price: u64,
+    payment: PricePayment,
+    entries: Vec<DropEntry>,
+) -> Result<()> {
+    let drop_table = &mut ctx.accounts.drop_table;
+    write_drop_table(drop_table, price, payment, entries)?;
+    drop_table.bump = ctx.bumps.drop_table;
+    
+    msg!("Drop table initialized with {} entries", drop_table.entries.len());
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateDropTable<'info> {
+    pub admin: Signer<'info>,
+    
+    #[account(
+        seeds = [seeds::CONFIG],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::DROP_TABLE],
+        bump = drop_table.bump,
+    )]
+    pub drop_table: Account<'info, DropTable>,
+}
+
+/// Replaces the drop table. Requests that are already committed are revealed
+/// against whatever table is live at reveal time.
+pub fn set_drop_table(
+    ctx: Context<UpdateDropTable>,
+    price: u64,
+    payment: PricePayment,
+    entries: Vec<DropEntry>,
+) -> Result<()> {
+    let drop_table = &mut ctx.accounts.drop_table;
+    write_drop_table(drop_table, price, payment, entries)?;
+    
+    msg!("Drop table set: {} entries, {} $ZAP", drop_table.entries.len(), price);
+    Ok(())
+}
+
+/// Weighted (type, rarity) pairs a weapon roll picks from.
+///
+/// Rolls always pick from the whole table, sold-out pairs included, so the
+/// odds are exactly the published weights. A roll that lands on a pair at its
+/// supply cap can't be minted; the player gets the price back with
+/// `refund_sold_out_mint_request` instead.
+#[account]
+#[derive(InitSpace)]
+pub struct DropTable {
+    /// $ZAP charged per mint request, held in escrow until it's revealed.
+    pub price: u64,
+    pub payment: PricePayment,
+    #[max_len(MAX_DROP_ENTRIES)]
+    pub entries: Vec<DropEntry>,
+    pub bump: u8,
+}
+
+impl DropTable {
+    pub fn validate(entries: &[DropEntry]) -> Result<()> {
+        require!(
+            !entries.is_empty() && entries.len() <= MAX_DROP_ENTRIES,
+            ErrorCode::InvalidDropTable
+        );
+        require!(
+            entries.iter().all(|entry| entry.weight > 0),
+            ErrorCode::InvalidDropTable
+        );
+        Ok(())
+    }
+    
+    pub fn total_weight(&self) -> u64 {
+        self.entries.iter().map(|entry| entry.weight as u64).sum()
+    }
+    
+    /// Entry whose cumulative weight range contains `roll % total_weight`.
+    /// `None` only for a table with no weight at all.
+    pub fn pick(&self, roll: u64) -> Option<&DropEntry> {
+        let total_weight = self.total_weight();
+        if total_weight == 0 {
+            return None;
+        }
+        
+        let mut remaining = roll % total_weight;
+        self.entries.iter().find(|entry| {
+            let hit = remaining < entry.weight as u64;
+            remaining = remaining.saturating_sub(entry.weight as u64);
+            hit
+        })
+    }
+    
+    /// Whether any entry can still be minted. Requests are refused once none
+    /// can, since every roll would then end in a refund.
+    pub fn has_supply(&self, weapon_supply: &WeaponSupply) -> bool {
+        self.entries
+            .iter()
+            .any(|entry| weapon_supply.remaining(&entry.weapon_type, &entry.rarity) > 0)
+    }
+}
+
+/// Replaces a drop table's price and entries, emitting `DropTableSet`.
+pub fn write_drop_table(
+    drop_table: &mut Account<DropTable>,
+    price: u64,
+    payment: PricePayment,
+    entries: Vec<DropEntry>,
+) -> Result<()> {
+    DropTable::validate(&entries)?;
+    
+    drop_table.price = price;
+    drop_table.payment = payment;
+    drop_table.entries = entries;
+    
+    emit!(DropTableSet {
+        drop_table: drop_table.key(),
+        price,
+        payment,
+        entries: drop_table.entries.clone(),
+    });
+    Ok(())
+}
+
+/// Where a drop table's price goes.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum PricePayment {
+    Burn,
+    /// Transferred to the config's `fee_recipient`.
+    FeeRecipient,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, InitSpace)]
+pub struct DropEntry {
+    pub weapon_type: WeaponType,
+    pub rarity: WeaponRarity,
+    pub weight: u32,
+}
+
+/// Finds the hash of the first slot at or after `target_slot` in the raw
+/// SlotHashes sysvar data. Slots can be skipped, so the target itself may
+/// never get a hash.
+///
+/// Fails with `RevealNotReady` until such a slot has been produced, and with
+/// `RevealExpired` once the sysvar no longer reaches back far enough to tell
+/// which slot that was.
+pub fn slot_hash_at_or_after(slot_hashes: &[u8], target_slot: u64) -> Result<(u64, [u8; 32])> {
+    let len_bytes = slot_hashes
+        .get(..SLOT_HASHES_LEN_SIZE)
+        .ok_or(ErrorCode::InvalidSlotHashes)?;
+    let len = u64::from_le_bytes(len_bytes.try_into().unwrap()) as usize;
+    
+    let mut found = None;
+    for i in 0..len {
+        let start = SLOT_HASHES_LEN_SIZE + i * SLOT_HASH_ENTRY_SIZE;
+        let entry = slot_hashes
+            .get(start..start + SLOT_HASH_ENTRY_SIZE)
+            .ok_or(ErrorCode::InvalidSlotHashes)?;
+        let slot = u64::from_le_bytes(entry[..8].try_into().unwrap());
+        if slot < target_slot {
+            // Every slot between here and `found` was skipped, so `found`
+            // really is the first one at or after the target.
+            return found.ok_or_else(|| ErrorCode::RevealNotReady.into());
+        }
+        found = Some((slot, entry[8..].try_into().unwrap()));
+    }
+    
+    match found {
+        Some((slot, hash)) if slot == target_slot => Ok((slot, hash)),
+        Some(_) => err!(ErrorCode::RevealExpired),
+        None => err!(ErrorCode::RevealNotReady),
+    }
+}
+
+/// The roll for a mint request: anyone can recompute it from the request
+/// address, its commit slot and the public slot hash.
+pub fn weapon_roll(slot_hash: &[u8; 32], request: &Pubkey, requested_slot: u64) -> u64 {
+    let hash = hashv(&[
+        WEAPON_ROLL_TAG,
+        slot_hash,
+        request.as_ref(),
+        &requested_slot.to_le_bytes(),
+    ]);
+    u64::from_le_bytes(hash.to_bytes()[..8].try_into().unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    /// SlotHashes sysvar data for `slots`, newest first, each hashed to its
+    /// own slot number.
+    fn slot_hashes(slots: &[u64]) -> Vec<u8> {
+        let mut data = (slots.len() as u64).to_le_bytes().to_vec();
+        for slot in slots {
+            data.extend_from_slice(&slot.to_le_bytes());
+            data.extend_from_slice(&[*slot as u8; 32]);
+        }
+        data
+    }
+    
+    fn entry(weapon_type: WeaponType, weight: u32) -> DropEntry {
+        DropEntry {
+            weapon_type,
+            rarity: WeaponRarity::Common,
+            weight,
+        }
+    }
+    
+    fn drop_table(entries: Vec<DropEntry>) -> DropTable {
+        DropTable {
+            price: 0,
+            payment: PricePayment::Burn,
+            entries,
+            bump: 0,
+        }
+    }
+    
+    fn weapon_supply() -> WeaponSupply {
+        WeaponSupply {
+            minted: [[0; WeaponRarity::COUNT]; WeaponType::COUNT],
+            caps: [[u32::MAX; WeaponRarity::COUNT]; WeaponType::COUNT],
+            bump: 0,
+        }
+    }
+    
+    #[test]
+    fn slot_hash_of_the_target_slot() {
+        let data = slot_hashes(&[12, 11, 10, 9]);
+        assert_eq!(slot_hash_at_or_after(&data, 10).unwrap(), (10, [10; 32]));
+        assert_eq!(slot_hash_at_or_after(&data, 12).unwrap(), (12, [12; 32]));
+    }
+    
+    #[test]
+    fn slot_hash_after_a_skipped_target() {
+        let data = slot_hashes(&[13, 11, 8]);
+        assert_eq!(slot_hash_at_or_after(&data, 10).unwrap(), (11, [11; 32]));
+        assert_eq!(slot_hash_at_or_after(&data, 9).unwrap(), (11, [11; 32]));
+    }
+    
+    #[test]
+    fn slot_hash_not_ready_before_the_target() {
+        let data = slot_hashes(&[9, 8]);
+        assert_eq!(
+            slot_hash_at_or_after(&data, 10).unwrap_err(),
+            ErrorCode::RevealNotReady.into()
+        );
+        assert_eq!(
+            slot_hash_at_or_after(&slot_hashes(&[]), 10).unwrap_err(),
+            ErrorCode::RevealNotReady.into()
+        );
+    }
+    
+    #[test]
+    fn slot_hash_expired_once_the_target_is_out_of_range() {
+        // The oldest hash is past the target, so the slots in between may or
+        // may not have been skipped.
+        let data = slot_hashes(&[20, 19]);
+        assert_eq!(
+            slot_hash_at_or_after(&data, 10).unwrap_err(),
+            ErrorCode::RevealExpired.into()
+        );
+        // Still known when the target is exactly the oldest hash.
+        assert_eq!(slot_hash_at_or_after(&data, 19).unwrap(), (19, [19; 32]));
+    }
+    
+    #[test]
+    fn slot_hash_rejects_truncated_data() {
+        let mut data = slot_hashes(&[12, 11]);
+        data.truncate(data.len() - 1);
+        assert_eq!(
+            slot_hash_at_or_after(&data, 10).unwrap_err(),
+            ErrorCode::InvalidSlotHashes.into()
+        );
+        assert_eq!(
+            slot_hash_at_or_after(&[0; 4], 10).unwrap_err(),
+            ErrorCode::InvalidSlotHashes.into()
+        );
+    }
+    
+    #[test]
+    fn pick_follows_cumulative_weights() {
+        let table = drop_table(vec![entry(WeaponType::AK47, 1), entry(WeaponType::M16, 3)]);
+        let picks: Vec<_> = (0..5)
+            .map(|roll| table.pick(roll).unwrap().weapon_type.clone())
+            .collect();
+        assert_eq!(
+            picks,
+            vec![
+                WeaponType::AK47,
+                WeaponType::M16,
+                WeaponType::M16,
+                WeaponType::M16,
+                WeaponType::AK47,
+            ]
+        );
+    }
+    
+    #[test]
+    fn pick_never_lands_on_zero_weight() {
+        let table = drop_table(vec![
+            entry(WeaponType::AK47, 1),
+            entry(WeaponType::M16, 0),
+            entry(WeaponType::Raygun, 1),
+        ]);
+        for roll in 0..10 {
+            assert_ne!(table.pick(roll).unwrap().weapon_type, WeaponType::M16);
+        }
+        
+        assert!(drop_table(vec![entry(WeaponType::AK47, 0)]).pick(7).is_none());
+        assert!(drop_table(vec![]).pick(7).is_none());
+    }
+    
+    #[test]
+    fn pick_includes_sold_out_entries() {
+        let table = drop_table(vec![entry(WeaponType::AK47, 1), entry(WeaponType::M16, 1)]);
+        let mut supply = weapon_supply();
+        supply.caps[WeaponType::AK47.index()][WeaponRarity::Common.index()] = 0;
+        
+        // The odds don't shift to what's left; the sold-out roll is refunded.
+        assert_eq!(table.pick(0).unwrap().weapon_type, WeaponType::AK47);
+        assert!(table.has_supply(&supply));
+        
+        supply.caps[WeaponType::M16.index()][WeaponRarity::Common.index()] = 0;
+        assert!(!table.has_supply(&supply));
+    }
+}
@@ -0,0 +1,412 @@
+use anchor_lang::prelude::*;
+
+use crate::achievements::AchievementCriteria;
+use crate::challenges::{ChallengeObjective, ChallengeWindow};
+use crate::drops::{DropEntry, PricePayment};
+use crate::nft::{WeaponRarity, WeaponType};
+use crate::perks::PerkEffect;
+use crate::stats::MatchStats;
+use crate::tournament::TournamentStatus;
+use crate::{EarnReason, PerkType};
+
+// Structured counterparts of the `msg!` logs, one per state change, so
+// indexers can follow the program without parsing free-form text.
+
+// Config
+
+#[event]
+pub struct ConfigInitialized {
+    pub admin: Pubkey,
+    pub game_server: Pubkey,
+    pub reward_oracle: Pubkey,
+    pub fee_recipient: Pubkey,
+}
+
+#[event]
+pub struct AdminProposed {
+    pub admin: Pubkey,
+    pub pending_admin: Pubkey,
+}
+
+#[event]
+pub struct AdminAccepted {
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct GameServerAdded {
+    pub game_server: Pubkey,
+}
+
+#[event]
+pub struct GameServerRemoved {
+    pub game_server: Pubkey,
+}
+
+#[event]
+pub struct RewardOracleSet {
+    pub reward_oracle: Pubkey,
+}
+
+#[event]
+pub struct FeeRecipientSet {
+    pub fee_recipient: Pubkey,
+}
+
+#[event]
+pub struct FeaturesSet {
+    pub features: u32,
+}
+
+#[event]
+pub struct PausedSet {
+    pub paused: u8,
+}
+
+#[event]
+pub struct EarnCapSet {
+    pub reason: EarnReason,
+    pub cap: u64,
+}
+
+// Multisig
+
+#[event]
+pub struct MultisigCreated {
+    pub owners: Vec<Pubkey>,
+    pub threshold: u8,
+    pub timelock: i64,
+}
+
+#[event]
+pub struct ProposalCreated {
+    pub proposal: Pubkey,
+    pub index: u64,
+    pub proposer: Pubkey,
+}
+
+#[event]
+pub struct ProposalApproved {
+    pub proposal: Pubkey,
+    pub owner: Pubkey,
+    pub approvals: u32,
+    /// Set once the proposal has reached its threshold.
+    pub approved_at: Option<i64>,
+}
+
+#[event]
+pub struct ProposalExecuted {
+    pub proposal: Pubkey,
+    pub index: u64,
+}
+
+#[event]
+pub struct MultisigOwnersSet {
+    pub owners: Vec<Pubkey>,
+    pub threshold: u8,
+}
+
+#[event]
+pub struct MultisigTimelockSet {
+    pub timelock: i64,
+}
+
+// $ZAP
+
+#[event]
+pub struct TokenInitialized {
+    pub mint: Pubkey,
+    pub initial_supply: u64,
+    pub decimals: u8,
+    pub max_supply: u64,
+}
+
+/// Emitted for every $ZAP mint, whatever instruction triggered it.
+#[event]
+pub struct TokensMinted {
+    pub mint: Pubkey,
+    /// Token account the $ZAP was minted to.
+    pub to: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct TokensBurned {
+    pub player: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub total_burned: u64,
+}
+
+#[event]
+pub struct TokensEarned {
+    pub player: Pubkey,
+    pub amount: u64,
+    pub reason: EarnReason,
+    pub nonce: u64,
+    pub daily_earned: u64,
+}
+
+// Perks
+
+#[event]
+pub struct PerkActivated {
+    pub player: Pubkey,
+    pub perk: PerkType,
+    pub price: u64,
+    pub expires_at: i64,
+}
+
+#[event]
+pub struct PerkDeactivated {
+    pub player: Pubkey,
+    pub perk: PerkType,
+}
+
+#[event]
+pub struct PerkTableUpdated {
+    pub perk: PerkType,
+    pub price: u64,
+    pub effect: PerkEffect,
+}
+
+#[event]
+pub struct ReviveConsumed {
+    pub player: Pubkey,
+    pub revive_credits: u8,
+}
+
+// Stats, seasons, achievements and challenges
+
+#[event]
+pub struct MatchRecorded {
+    pub player: Pubkey,
+    /// Season the match counted towards, if one was running.
+    pub season: Option<u32>,
+    pub stats: MatchStats,
+    pub highest_round: u32,
+    pub total_kills: u64,
+}
+
+#[event]
+pub struct SeasonStarted {
+    pub season: u32,
+    pub start_time: i64,
+    pub end_time: i64,
+}
+
+#[event]
+pub struct SeasonRewardClaimed {
+    pub season: u32,
+    pub player: Pubkey,
+    pub score: u64,
+    pub amount: u64,
+}
+
+#[event]
+pub struct AchievementCreated {
+    pub achievement: u32,
+    pub name: String,
+    pub criteria: AchievementCriteria,
+    pub zap_reward: u64,
+}
+
+#[event]
+pub struct AchievementUnlocked {
+    pub achievement: u32,
+    pub player: Pubkey,
+    pub badge_mint: Pubkey,
+    pub zap_reward: u64,
+}
+
+#[event]
+pub struct ChallengeCreated {
+    pub challenge: u32,
+    pub objective: ChallengeObjective,
+    pub target: u64,
+    pub reward: u64,
+    pub window: ChallengeWindow,
+    pub start_time: i64,
+    pub end_time: i64,
+}
+
+#[event]
+pub struct ChallengeCompleted {
+    pub challenge: u32,
+    pub player: Pubkey,
+    pub window_index: u64,
+    pub reward: u64,
+}
+
+// Weapons
+
+/// Emitted whenever a drop table or the mystery box table is set.
+#[event]
+pub struct DropTableSet {
+    pub drop_table: Pubkey,
+    pub price: u64,
+    pub payment: PricePayment,
+    pub entries: Vec<DropEntry>,
+}
+
+#[event]
+pub struct MysteryBoxOpened {
+    pub player: Pubkey,
+    pub request: Pubkey,
+    pub price: u64,
+    pub payment: PricePayment,
+}
+
+#[event]
+pub struct WeaponMintRequested {
+    pub player: Pubkey,
+    pub request: Pubkey,
+    pub drop_table: Pubkey,
+    pub price: u64,
+    pub target_slot: u64,
+}
+
+/// Everything needed to recompute a weapon roll with `drops::weapon_roll`
+/// and check it against the drop table.
+#[event]
+pub struct WeaponRolled {
+    pub player: Pubkey,
+    pub request: Pubkey,
+    pub drop_table: Pubkey,
+    /// Slot whose hash decided the roll.
+    pub slot: u64,
+    pub slot_hash: [u8; 32],
+    pub roll: u64,
+    pub total_weight: u64,
+    pub weapon_type: WeaponType,
+    pub rarity: WeaponRarity,
+}
+
+#[event]
+pub struct WeaponMintRefunded {
+    pub player: Pubkey,
+    pub request: Pubkey,
+    /// The sold-out pair the roll landed on.
+    pub weapon_type: WeaponType,
+    pub rarity: WeaponRarity,
+    pub amount: u64,
+}
+
+#[event]
+pub struct SupplyCapSet {
+    pub weapon_type: WeaponType,
+    pub rarity: WeaponRarity,
+    pub cap: u32,
+    pub minted: u32,
+}
+
+#[event]
+pub struct WeaponMinted {
+    pub player: Pubkey,
+    pub mint: Pubkey,
+    pub weapon_type: WeaponType,
+    pub rarity: WeaponRarity,
+    pub damage: u32,
+}
+
+#[event]
+pub struct WeaponOwnerSynced {
+    pub mint: Pubkey,
+    pub previous_owner: Pubkey,
+    pub owner: Pubkey,
+}
+
+#[event]
+pub struct WeaponLevelConfigSet {
+    pub xp_thresholds: Vec<u64>,
+    pub damage_growth_bps: u16,
+}
+
+#[event]
+pub struct WeaponKillsRecorded {
+    pub mint: Pubkey,
+    pub kills: u64,
+    pub xp: u64,
+    pub total_kills: u64,
+    pub total_xp: u64,
+    pub previous_level: u8,
+    pub level: u8,
+    pub damage: u32,
+}
+
+#[event]
+pub struct WeaponPackAPunched {
+    pub player: Pubkey,
+    pub mint: Pubkey,
+    pub cost: u64,
+    pub damage: u32,
+    pub level: u8,
+}
+
+// Tournaments and guilds
+
+#[event]
+pub struct TournamentCreated {
+    pub tournament: Pubkey,
+    pub organizer: Pubkey,
+    pub entry_fee: u64,
+    pub min_players: u32,
+    pub max_players: u32,
+    pub end_time: i64,
+    pub payout_bps: Vec<u16>,
+}
+
+#[event]
+pub struct TournamentJoined {
+    pub tournament: Pubkey,
+    pub player: Pubkey,
+    pub current_players: u32,
+    pub prize_pool: u64,
+}
+
+/// Emitted when a tournament is started, ended, cancelled or paid out.
+#[event]
+pub struct TournamentStatusChanged {
+    pub tournament: Pubkey,
+    pub status: TournamentStatus,
+}
+
+#[event]
+pub struct TournamentScoreUpdated {
+    pub tournament: Pubkey,
+    pub player: Pubkey,
+    pub round: u32,
+    pub kills: u64,
+    pub highest_round: u32,
+    pub total_kills: u64,
+    pub nonce: u64,
+}
+
+#[event]
+pub struct TournamentPrizePaid {
+    pub tournament: Pubkey,
+    pub player: Pubkey,
+    /// Zero-based finishing place.
+    pub place: u8,
+    pub amount: u64,
+}
+
+#[event]
+pub struct TournamentPrizesDistributed {
+    pub tournament: Pubkey,
+    pub total: u64,
+}
+
+#[event]
+pub struct TournamentRefunded {
+    pub tournament: Pubkey,
+    pub player: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct GuildCreated {
+    pub guild: Pubkey,
+    pub leader: Pubkey,
+    pub name: String,
+}
@@ -0,0 +1,193 @@
+use anchor_lang::prelude::*;
+use std::cmp::Ordering;
+
+use crate::config::ProgramConfig;
+use crate::{seeds, ErrorCode};
+
+/// Players kept on each global board.
+pub const GLOBAL_LEADERBOARD_SIZE: usize = 100;
+
+#[derive(Accounts)]
+pub struct InitializeGlobalLeaderboard<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        seeds = [seeds::CONFIG],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + GlobalLeaderboard::INIT_SPACE,
+        seeds = [seeds::GLOBAL_LEADERBOARD],
+        bump,
+    )]
+    pub global_leaderboard: Box<Account<'info, GlobalLeaderboard>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_global_leaderboard(ctx: Context<InitializeGlobalLeaderboard>) -> Result<()> {
+    let global_leaderboard = &mut ctx.accounts.global_leaderboard;
+    global_leaderboard.by_round = vec![];
+    global_leaderboard.by_kills = vec![];
+    global_leaderboard.bump = ctx.bumps.global_leaderboard;
+    
+    msg!("Global leaderboard initialized");
+    Ok(())
+}
+
+/// All-time top players, ranked two ways. Both boards are kept sorted so a
+/// single account fetch gives clients the full ranking.
+#[account]
+#[derive(InitSpace)]
+pub struct GlobalLeaderboard {
+    /// Ranked by `highest_round`, then `total_kills`.
+    #[max_len(GLOBAL_LEADERBOARD_SIZE)]
+    pub by_round: Vec<RankedPlayer>,
+    /// Ranked by `total_kills`, then `highest_round`.
+    #[max_len(GLOBAL_LEADERBOARD_SIZE)]
+    pub by_kills: Vec<RankedPlayer>,
+    pub bump: u8,
+}
+
+impl GlobalLeaderboard {
+    pub fn record(&mut self, player: Pubkey, highest_round: u32, total_kills: u64) {
+        let entry = RankedPlayer {
+            player,
+            highest_round,
+            total_kills,
+        };
+        upsert_ranked(&mut self.by_round, entry.clone(), RankedPlayer::cmp_by_round);
+        upsert_ranked(&mut self.by_kills, entry, RankedPlayer::cmp_by_kills);
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, InitSpace)]
+pub struct RankedPlayer {
+    pub player: Pubkey,
+    pub highest_round: u32,
+    pub total_kills: u64,
+}
+
+// Orderings put the better player first. Full ties fall back to the player
+// key so every board has exactly one valid order.
+impl RankedPlayer {
+    pub fn cmp_by_round(&self, other: &RankedPlayer) -> Ordering {
+        other
+            .highest_round
+            .cmp(&self.highest_round)
+            .then(other.total_kills.cmp(&self.total_kills))
+            .then(self.player.cmp(&other.player))
+    }
+    
+    pub fn cmp_by_kills(&self, other: &RankedPlayer) -> Ordering {
+        other
+            .total_kills
+            .cmp(&self.total_kills)
+            .then(other.highest_round.cmp(&self.highest_round))
+            .then(self.player.cmp(&other.player))
+    }
+}
+
+fn upsert_ranked(
+    board: &mut Vec<RankedPlayer>,
+    entry: RankedPlayer,
+    cmp: fn(&RankedPlayer, &RankedPlayer) -> Ordering,
+) {
+    board.retain(|ranked| ranked.player != entry.player);
+    
+    let position = board
+        .iter()
+        .position(|ranked| cmp(&entry, ranked) == Ordering::Less)
+        .unwrap_or(board.len());
+    if position < GLOBAL_LEADERBOARD_SIZE {
+        board.insert(position, entry);
+        board.truncate(GLOBAL_LEADERBOARD_SIZE);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn key(byte: u8) -> Pubkey {
+        Pubkey::new_from_array([byte; 32])
+    }
+    
+    fn leaderboard() -> GlobalLeaderboard {
+        GlobalLeaderboard {
+            by_round: vec![],
+            by_kills: vec![],
+            bump: 0,
+        }
+    }
+    
+    fn players(board: &[RankedPlayer]) -> Vec<Pubkey> {
+        board.iter().map(|ranked| ranked.player).collect()
+    }
+    
+    #[test]
+    fn record_ranks_each_board_by_its_own_stat() {
+        let mut leaderboard = leaderboard();
+        leaderboard.record(key(1), 20, 100);
+        leaderboard.record(key(2), 10, 500);
+        leaderboard.record(key(3), 20, 300);
+        assert_eq!(players(&leaderboard.by_round), vec![key(3), key(1), key(2)]);
+        assert_eq!(players(&leaderboard.by_kills), vec![key(2), key(3), key(1)]);
+    }
+    
+    #[test]
+    fn record_breaks_full_ties_by_player_key() {
+        let mut leaderboard = leaderboard();
+        leaderboard.record(key(9), 20, 100);
+        leaderboard.record(key(1), 20, 100);
+        leaderboard.record(key(5), 20, 100);
+        // Same order whatever order the scores arrived in.
+        assert_eq!(players(&leaderboard.by_round), vec![key(1), key(5), key(9)]);
+        assert_eq!(players(&leaderboard.by_kills), vec![key(1), key(5), key(9)]);
+    }
+    
+    #[test]
+    fn record_replaces_the_players_previous_entry() {
+        let mut leaderboard = leaderboard();
+        leaderboard.record(key(1), 20, 100);
+        leaderboard.record(key(2), 10, 50);
+        leaderboard.record(key(2), 30, 200);
+        assert_eq!(players(&leaderboard.by_round), vec![key(2), key(1)]);
+        assert_eq!(leaderboard.by_round[0].highest_round, 30);
+        assert_eq!(leaderboard.by_kills.len(), 2);
+    }
+    
+    #[test]
+    fn full_board_evicts_its_lowest_entry() {
+        let mut leaderboard = leaderboard();
+        for rank in 0..GLOBAL_LEADERBOARD_SIZE {
+            leaderboard.record(Pubkey::new_unique(), rank as u32 + 1, rank as u64 + 1);
+        }
+        let lowest = leaderboard.by_round.last().unwrap().player;
+        
+        leaderboard.record(key(1), 1_000, 1_000);
+        assert_eq!(leaderboard.by_round.len(), GLOBAL_LEADERBOARD_SIZE);
+        assert_eq!(leaderboard.by_round[0].player, key(1));
+        assert!(!players(&leaderboard.by_round).contains(&lowest));
+        assert!(!players(&leaderboard.by_kills).contains(&lowest));
+    }
+    
+    #[test]
+    fn full_board_ignores_scores_below_its_lowest_entry() {
+        let mut leaderboard = leaderboard();
+        for rank in 0..GLOBAL_LEADERBOARD_SIZE {
+            leaderboard.record(Pubkey::new_unique(), rank as u32 + 1, rank as u64 + 1);
+        }
+        let board = leaderboard.by_round.clone();
+        
+        leaderboard.record(key(1), 0, 0);
+        assert_eq!(leaderboard.by_round, board);
+        assert!(!players(&leaderboard.by_kills).contains(&key(1)));
+    }
+}
@@ -0,0 +1,344 @@
+use anchor_lang::prelude::*;
+
+use crate::config::{ProgramConfig, PAUSE_NFT};
+use crate::events::{WeaponKillsRecorded, WeaponLevelConfigSet};
+use crate::nft::WeaponData;
+use crate::{seeds, ErrorCode};
+
+/// Highest level any weapon can reach, see `WeaponRarity::max_level`.
+pub const MAX_WEAPON_LEVEL: usize = 30;
+
+#[derive(Accounts)]
+pub struct InitializeWeaponLevels<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        seeds = [seeds::CONFIG],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + WeaponLevelConfig::INIT_SPACE,
+        seeds = [seeds::WEAPON_LEVELS],
+        bump,
+    )]
+    pub weapon_levels: Account<'info, WeaponLevelConfig>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_weapon_levels(
+    ctx: Context<InitializeWeaponLevels>,
+    xp_thresholds: Vec<u64>,
+    damage_growth_bps: u16,
+) -> Result<()> {
+    let weapon_levels = &mut ctx.accounts.weapon_levels;
+    weapon_levels.set(xp_thresholds, damage_growth_bps)?;
+    weapon_levels.bump = ctx.bumps.weapon_levels;
+    
+    emit!(WeaponLevelConfigSet {
+        xp_thresholds: weapon_levels.xp_thresholds.clone(),
+        damage_growth_bps,
+    });
+    msg!("Weapon levels initialized: {} thresholds", weapon_levels.xp_thresholds.len());
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateWeaponLevels<'info> {
+    pub admin: Signer<'info>,
+    
+    #[account(
+        seeds = [seeds::CONFIG],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::WEAPON_LEVELS],
+        bump = weapon_levels.bump,
+    )]
+    pub weapon_levels: Account<'info, WeaponLevelConfig>,
+}
+
+/// Replaces the level curve. Weapons keep the levels they already have and
+/// move on the new curve from their next `record_weapon_kills`.
+pub fn set_weapon_levels(
+    ctx: Context<UpdateWeaponLevels>,
+    xp_thresholds: Vec<u64>,
+    damage_growth_bps: u16,
+) -> Result<()> {
+    let weapon_levels = &mut ctx.accounts.weapon_levels;
+    weapon_levels.set(xp_thresholds, damage_growth_bps)?;
+    
+    emit!(WeaponLevelConfigSet {
+        xp_thresholds: weapon_levels.xp_thresholds.clone(),
+        damage_growth_bps,
+    });
+    msg!("Weapon levels set: {} thresholds", weapon_levels.xp_thresholds.len());
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RecordWeaponKills<'info> {
+    #[account(
+        constraint = config.is_game_server(&game_server.key()) @ ErrorCode::Unauthorized,
+    )]
+    pub game_server: Signer<'info>,
+    
+    #[account(
+        seeds = [seeds::CONFIG],
+        bump = config.bump,
+        constraint = !config.is_paused(PAUSE_NFT) @ ErrorCode::ProgramPaused,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        seeds = [seeds::WEAPON_LEVELS],
+        bump = weapon_levels.bump,
+    )]
+    pub weapon_levels: Account<'info, WeaponLevelConfig>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::WEAPON, weapon_data.mint.as_ref()],
+        bump,
+    )]
+    pub weapon_data: Account<'info, WeaponData>,
+}
+
+/// Adds kills and XP reported by the game server, levelling the weapon up
+/// for every threshold its XP has crossed, up to its rarity's max level.
+pub fn record_weapon_kills(ctx: Context<RecordWeaponKills>, kills: u64, xp: u64) -> Result<()> {
+    let weapon_levels = &ctx.accounts.weapon_levels;
+    let weapon_data = &mut ctx.accounts.weapon_data;
+    weapon_data.kills = weapon_data.kills.saturating_add(kills);
+    weapon_data.xp = weapon_data.xp.saturating_add(xp);
+    
+    let previous_level = weapon_data.level;
+    weapon_levels.level_up(weapon_data);
+    
+    emit!(WeaponKillsRecorded {
+        mint: weapon_data.mint,
+        kills,
+        xp,
+        total_kills: weapon_data.kills,
+        total_xp: weapon_data.xp,
+        previous_level,
+        level: weapon_data.level,
+        damage: weapon_data.damage,
+    });
+    msg!(
+        "Weapon {}: +{} kills, +{} XP, level {}",
+        weapon_data.mint,
+        kills,
+        xp,
+        weapon_data.level
+    );
+    Ok(())
+}
+
+/// XP curve shared by all weapons.
+#[account]
+#[derive(InitSpace)]
+pub struct WeaponLevelConfig {
+    /// Total XP needed for each level after the first, strictly increasing:
+    /// entry `i` is what a weapon needs to gain its `i + 1`th level.
+    #[max_len(MAX_WEAPON_LEVEL)]
+    pub xp_thresholds: Vec<u64>,
+    /// Damage increase per level in basis points, compounding.
+    pub damage_growth_bps: u16,
+    pub bump: u8,
+}
+
+impl WeaponLevelConfig {
+    fn set(&mut self, xp_thresholds: Vec<u64>, damage_growth_bps: u16) -> Result<()> {
+        require!(
+            xp_thresholds.len() < MAX_WEAPON_LEVEL,
+            ErrorCode::InvalidWeaponLevels
+        );
+        require!(
+            xp_thresholds.windows(2).all(|pair| pair[0] < pair[1]),
+            ErrorCode::InvalidWeaponLevels
+        );
+        
+        self.xp_thresholds = xp_thresholds;
+        self.damage_growth_bps = damage_growth_bps;
+        Ok(())
+    }
+    
+    /// Levels gained from `xp`, not counting the starting level.
+    pub fn levels_for(&self, xp: u64) -> u8 {
+        self.xp_thresholds
+            .iter()
+            .take_while(|threshold| xp >= **threshold)
+            .count() as u8
+    }
+    
+    /// Raises `weapon_data` to the level its XP has earned, capped at its
+    /// rarity's max level, growing its damage once per level gained. Weapons
+    /// never lose levels.
+    pub fn level_up(&self, weapon_data: &mut WeaponData) {
+        // Pack-a-Punch grants a level of its own on top of the XP levels.
+        let earned_level =
+            1 + weapon_data.is_pack_a_punched as u8 + self.levels_for(weapon_data.xp);
+        let new_level = earned_level.min(weapon_data.rarity.max_level());
+        let levels_gained = new_level.saturating_sub(weapon_data.level);
+        if levels_gained > 0 {
+            weapon_data.damage = self.grow_damage(weapon_data.damage, levels_gained);
+            weapon_data.level = new_level;
+        }
+    }
+    
+    /// `damage` after `levels` rounds of `damage_growth_bps`.
+    pub fn grow_damage(&self, damage: u32, levels: u8) -> u32 {
+        let growth = 10_000 + self.damage_growth_bps as u64;
+        let mut damage = damage as u64;
+        for _ in 0..levels {
+            damage = (damage * growth / 10_000).min(u32::MAX as u64);
+        }
+        damage as u32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nft::{WeaponRarity, WeaponType};
+    
+    fn level_config(xp_thresholds: Vec<u64>) -> WeaponLevelConfig {
+        WeaponLevelConfig {
+            xp_thresholds,
+            damage_growth_bps: 1_000,
+            bump: 0,
+        }
+    }
+    
+    fn weapon(rarity: WeaponRarity) -> WeaponData {
+        WeaponData {
+            weapon_type: WeaponType::AK47,
+            rarity,
+            level: 1,
+            damage: 100,
+            kills: 0,
+            xp: 0,
+            is_pack_a_punched: false,
+            owner: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            created_at: 0,
+        }
+    }
+    
+    #[test]
+    fn levels_for_counts_crossed_thresholds() {
+        let weapon_levels = level_config(vec![100, 300, 600]);
+        assert_eq!(weapon_levels.levels_for(0), 0);
+        assert_eq!(weapon_levels.levels_for(99), 0);
+        assert_eq!(weapon_levels.levels_for(100), 1);
+        assert_eq!(weapon_levels.levels_for(299), 1);
+        assert_eq!(weapon_levels.levels_for(300), 2);
+        assert_eq!(weapon_levels.levels_for(u64::MAX), 3);
+        assert_eq!(level_config(vec![]).levels_for(u64::MAX), 0);
+    }
+    
+    #[test]
+    fn grow_damage_compounds_and_saturates() {
+        let weapon_levels = level_config(vec![]);
+        assert_eq!(weapon_levels.grow_damage(100, 0), 100);
+        assert_eq!(weapon_levels.grow_damage(100, 1), 110);
+        // 100 -> 110 -> 121, rounding down each level.
+        assert_eq!(weapon_levels.grow_damage(100, 2), 121);
+        assert_eq!(weapon_levels.grow_damage(15, 1), 16);
+        assert_eq!(weapon_levels.grow_damage(u32::MAX, 3), u32::MAX);
+    }
+    
+    #[test]
+    fn set_requires_increasing_thresholds_below_the_max_level() {
+        let mut weapon_levels = level_config(vec![]);
+        assert!(weapon_levels.set(vec![100, 200], 500).is_ok());
+        assert_eq!(
+            weapon_levels.set(vec![100, 100], 500).unwrap_err(),
+            ErrorCode::InvalidWeaponLevels.into()
+        );
+        assert_eq!(
+            weapon_levels.set(vec![200, 100], 500).unwrap_err(),
+            ErrorCode::InvalidWeaponLevels.into()
+        );
+        let too_many = (1..=MAX_WEAPON_LEVEL as u64).collect();
+        assert_eq!(
+            weapon_levels.set(too_many, 500).unwrap_err(),
+            ErrorCode::InvalidWeaponLevels.into()
+        );
+    }
+    
+    #[test]
+    fn level_up_at_threshold_boundaries() {
+        let weapon_levels = level_config(vec![100, 300]);
+        let mut weapon = weapon(WeaponRarity::Rare);
+        
+        weapon.xp = 99;
+        weapon_levels.level_up(&mut weapon);
+        assert_eq!((weapon.level, weapon.damage), (1, 100));
+        
+        weapon.xp = 100;
+        weapon_levels.level_up(&mut weapon);
+        assert_eq!((weapon.level, weapon.damage), (2, 110));
+        
+        // Two thresholds at once grow damage twice.
+        weapon.level = 1;
+        weapon.damage = 100;
+        weapon.xp = 300;
+        weapon_levels.level_up(&mut weapon);
+        assert_eq!((weapon.level, weapon.damage), (3, 121));
+    }
+    
+    #[test]
+    fn level_up_counts_pack_a_punch_as_a_level() {
+        let weapon_levels = level_config(vec![100]);
+        let mut weapon = weapon(WeaponRarity::Rare);
+        weapon.is_pack_a_punched = true;
+        weapon.level = 2;
+        
+        weapon.xp = 100;
+        weapon_levels.level_up(&mut weapon);
+        assert_eq!((weapon.level, weapon.damage), (3, 110));
+    }
+    
+    #[test]
+    fn level_up_stops_at_the_rarity_max_level() {
+        let weapon_levels = level_config((1..=10).map(|level| level * 100).collect());
+        let mut weapon = weapon(WeaponRarity::Common);
+        
+        weapon.xp = 399;
+        weapon_levels.level_up(&mut weapon);
+        assert_eq!(weapon.level, 4);
+        
+        weapon.xp = 400;
+        weapon_levels.level_up(&mut weapon);
+        assert_eq!(weapon.level, WeaponRarity::Common.max_level());
+        let capped_damage = weapon.damage;
+        
+        weapon.xp = u64::MAX;
+        weapon_levels.level_up(&mut weapon);
+        assert_eq!(weapon.level, WeaponRarity::Common.max_level());
+        assert_eq!(weapon.damage, capped_damage);
+    }
+    
+    #[test]
+    fn level_up_never_lowers_a_level() {
+        // A steeper curve set after the weapon levelled up.
+        let weapon_levels = level_config(vec![1_000]);
+        let mut weapon = weapon(WeaponRarity::Rare);
+        weapon.level = 3;
+        weapon.xp = 500;
+        weapon_levels.level_up(&mut weapon);
+        assert_eq!((weapon.level, weapon.damage), (3, 100));
+    }
+}
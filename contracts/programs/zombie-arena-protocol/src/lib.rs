@@ -1,29 +1,167 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar;
 use anchor_spl::token::{self, Burn, Mint, MintTo, Token, TokenAccount, Transfer};
 
+pub mod achievements;
+pub mod attestation;
+pub mod challenges;
+pub mod config;
+pub mod drops;
+pub mod events;
+pub mod leaderboard;
+pub mod leveling;
+pub mod multisig;
+pub mod mystery_box;
 pub mod nft;
+pub mod perks;
+pub mod season;
+pub mod seeds;
+pub mod stats;
+pub mod supply;
 pub mod tournament;
 
+use achievements::*;
+use challenges::*;
+use config::*;
+use drops::*;
+use events::*;
+use leaderboard::*;
+use leveling::*;
+use multisig::*;
+use mystery_box::*;
 use nft::*;
+use perks::*;
+use season::*;
+use stats::*;
+use supply::*;
 use tournament::*;
 
 declare_id!("ZAPxxx111111111111111111111111111111111111");
 
+pub const SECONDS_PER_DAY: i64 = 86_400;
+
+/// Perks a player can have active at once before any `ExtraPerkSlots` effect.
+pub const BASE_PERK_SLOTS: usize = 4;
+
+/// Upper bound on active perks, however many extra slots perks grant.
+pub const MAX_PERK_SLOTS: usize = PerkType::COUNT;
+
+/// How long a perk bought with `burn_for_perk` lasts: one play session.
+pub const PERK_DURATION: i64 = 60 * 60;
+
 #[program]
 pub mod zombie_arena_protocol {
     use super::*;
 
+    pub fn initialize_config(
+        ctx: Context<InitializeConfig>,
+        game_server: Pubkey,
+        reward_oracle: Pubkey,
+        fee_recipient: Pubkey,
+    ) -> Result<()> {
+        config::initialize_config(ctx, game_server, reward_oracle, fee_recipient)
+    }
+    
+    pub fn propose_admin(ctx: Context<UpdateConfig>, new_admin: Pubkey) -> Result<()> {
+        config::propose_admin(ctx, new_admin)
+    }
+    
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        config::accept_admin(ctx)
+    }
+    
+    pub fn add_game_server(ctx: Context<UpdateConfig>, game_server: Pubkey) -> Result<()> {
+        config::add_game_server(ctx, game_server)
+    }
+    
+    pub fn remove_game_server(ctx: Context<UpdateConfig>, game_server: Pubkey) -> Result<()> {
+        config::remove_game_server(ctx, game_server)
+    }
+    
+    pub fn set_reward_oracle(ctx: Context<UpdateConfig>, reward_oracle: Pubkey) -> Result<()> {
+        config::set_reward_oracle(ctx, reward_oracle)
+    }
+    
+    pub fn set_fee_recipient(ctx: Context<UpdateConfig>, fee_recipient: Pubkey) -> Result<()> {
+        config::set_fee_recipient(ctx, fee_recipient)
+    }
+    
+    pub fn set_feature(ctx: Context<UpdateConfig>, feature: u32, enabled: bool) -> Result<()> {
+        config::set_feature(ctx, feature, enabled)
+    }
+    
+    pub fn set_paused(ctx: Context<UpdateConfig>, paused: u8) -> Result<()> {
+        config::set_paused(ctx, paused)
+    }
+    
+    pub fn set_earn_cap(ctx: Context<UpdateConfig>, reason: EarnReason, cap: u64) -> Result<()> {
+        config::set_earn_cap(ctx, reason, cap)
+    }
+    
+    pub fn create_multisig(
+        ctx: Context<CreateMultisig>,
+        owners: Vec<Pubkey>,
+        threshold: u8,
+        timelock: i64,
+    ) -> Result<()> {
+        multisig::create_multisig(ctx, owners, threshold, timelock)
+    }
+    
+    pub fn create_proposal(
+        ctx: Context<CreateProposal>,
+        program_id: Pubkey,
+        accounts: Vec<ProposalAccount>,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        multisig::create_proposal(ctx, program_id, accounts, data)
+    }
+    
+    pub fn approve_proposal(ctx: Context<ApproveProposal>) -> Result<()> {
+        multisig::approve_proposal(ctx)
+    }
+    
+    pub fn execute_proposal<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteProposal<'info>>,
+    ) -> Result<()> {
+        multisig::execute_proposal(ctx)
+    }
+    
+    pub fn set_multisig_owners(
+        ctx: Context<UpdateMultisig>,
+        owners: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        multisig::set_multisig_owners(ctx, owners, threshold)
+    }
+    
+    pub fn set_multisig_timelock(ctx: Context<UpdateMultisig>, timelock: i64) -> Result<()> {
+        multisig::set_multisig_timelock(ctx, timelock)
+    }
+    
     pub fn initialize_token(
         ctx: Context<InitializeToken>,
         initial_supply: u64,
         decimals: u8,
+        emission: EmissionSchedule,
     ) -> Result<()> {
+        emission.validate()?;
+        
         let token_data = &mut ctx.accounts.token_data;
-        token_data.admin = ctx.accounts.admin.key();
         token_data.mint = ctx.accounts.mint.key();
         token_data.total_burned = 0;
+        token_data.total_minted = 0;
+        token_data.emission = emission;
+        token_data.genesis_time = Clock::get()?.unix_timestamp;
+        token_data.current_epoch = 0;
+        token_data.epoch_minted = 0;
         token_data.bump = ctx.bumps.token_data;
         
+        emit!(TokenInitialized {
+            mint: token_data.mint,
+            initial_supply,
+            decimals,
+            max_supply: token_data.emission.max_supply,
+        });
         msg!("$ZAP Token initialized with supply: {}", initial_supply);
         Ok(())
     }
@@ -32,44 +170,104 @@ pub mod zombie_arena_protocol {
         ctx: Context<MintTokens>,
         amount: u64,
     ) -> Result<()> {
-        require!(
-            ctx.accounts.admin.key() == ctx.accounts.token_data.admin,
-            ErrorCode::Unauthorized
-        );
+        let now = Clock::get()?.unix_timestamp;
+        let token_data = &mut ctx.accounts.token_data;
+        token_data.record_mint(amount, now)?;
+        
+        // Anything that isn't a player, like a treasury, has no player state.
+        let recipient_info = &ctx.accounts.recipient_state;
+        if recipient_info.owner == ctx.program_id && !recipient_info.data_is_empty() {
+            let mut data = recipient_info.try_borrow_mut_data()?;
+            let mut recipient_state = PlayerState::try_deserialize(&mut &data[..])?;
+            let daily_cap = token_data.emission.player_daily_cap;
+            recipient_state.record_daily_earnings(amount, daily_cap, now)?;
+            recipient_state.try_serialize(&mut &mut data[..])?;
+        }
 
-        let cpi_accounts = MintTo {
-            mint: ctx.accounts.mint.to_account_info(),
-            to: ctx.accounts.token_account.to_account_info(),
-            authority: ctx.accounts.mint_authority.to_account_info(),
-        };
-        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
-        token::mint_to(cpi_ctx, amount)?;
+        mint_zap(
+            &ctx.accounts.token_program,
+            &ctx.accounts.mint,
+            &ctx.accounts.token_account,
+            &ctx.accounts.mint_authority,
+            ctx.bumps.mint_authority,
+            amount,
+        )?;
         
         Ok(())
     }
 
+    pub fn initialize_perk_table(ctx: Context<InitializePerkTable>) -> Result<()> {
+        perks::initialize_perk_table(ctx)
+    }
+    
+    pub fn set_perk_price(
+        ctx: Context<UpdatePerkTable>,
+        perk_type: PerkType,
+        price: u64,
+    ) -> Result<()> {
+        perks::set_perk_price(ctx, perk_type, price)
+    }
+    
+    pub fn set_perk_effect(
+        ctx: Context<UpdatePerkTable>,
+        perk_type: PerkType,
+        effect: PerkEffect,
+    ) -> Result<()> {
+        perks::set_perk_effect(ctx, perk_type, effect)
+    }
+    
+    pub fn consume_revive(ctx: Context<ConsumeRevive>) -> Result<()> {
+        perks::consume_revive(ctx)
+    }
+    
     pub fn burn_for_perk(
         ctx: Context<BurnForPerk>,
-        amount: u64,
         perk_type: PerkType,
     ) -> Result<()> {
-        let cpi_accounts = Burn {
-            mint: ctx.accounts.mint.to_account_info(),
-            from: ctx.accounts.player_token_account.to_account_info(),
-            authority: ctx.accounts.player.to_account_info(),
-        };
-        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
-        token::burn(cpi_ctx, amount)?;
-
-        let token_data = &mut ctx.accounts.token_data;
-        token_data.total_burned += amount;
+        let amount = ctx.accounts.perk_table.price(&perk_type)?;
+        
+        burn_zap(
+            &ctx.accounts.token_program,
+            &ctx.accounts.mint,
+            &ctx.accounts.player_token_account,
+            &ctx.accounts.player.to_account_info(),
+            &[],
+            &mut ctx.accounts.token_data,
+            ctx.accounts.player.key(),
+            amount,
+        )?;
 
+        let now = Clock::get()?.unix_timestamp;
         let player_state = &mut ctx.accounts.player_state;
-        player_state.activate_perk(perk_type)?;
+        player_state.activate_perk(
+            perk_type.clone(),
+            now,
+            PERK_DURATION,
+            &ctx.accounts.perk_table,
+        )?;
         
+        emit!(PerkActivated {
+            player: player_state.player,
+            perk: perk_type.clone(),
+            price: amount,
+            expires_at: now + PERK_DURATION,
+        });
         msg!("Burned {} $ZAP for perk: {:?}", amount, perk_type);
         Ok(())
     }
+    
+    pub fn deactivate_perk(ctx: Context<DeactivatePerk>, perk_type: PerkType) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let player_state = &mut ctx.accounts.player_state;
+        player_state.deactivate_perk(&perk_type, now, &ctx.accounts.perk_table)?;
+        
+        emit!(PerkDeactivated {
+            player: player_state.player,
+            perk: perk_type.clone(),
+        });
+        msg!("Deactivated perk: {:?}", perk_type);
+        Ok(())
+    }
 
     pub fn create_player_state(
         ctx: Context<CreatePlayerState>,
@@ -78,8 +276,19 @@ pub mod zombie_arena_protocol {
         player_state.player = ctx.accounts.player.key();
         player_state.total_kills = 0;
         player_state.highest_round = 0;
+        player_state.total_headshots = 0;
+        player_state.total_revives = 0;
+        player_state.total_rounds_survived = 0;
+        player_state.total_play_time = 0;
+        player_state.matches_played = 0;
+        player_state.season_stats = SeasonStats::default();
+        player_state.previous_season_stats = SeasonStats::default();
         player_state.tokens_earned = 0;
+        player_state.earn_nonce = 0;
+        player_state.earn_day = 0;
+        player_state.daily_earned = 0;
         player_state.active_perks = vec![];
+        player_state.revive_credits = 0;
         player_state.bump = ctx.bumps.player_state;
         
         Ok(())
@@ -89,41 +298,223 @@ pub mod zombie_arena_protocol {
         ctx: Context<EarnTokens>,
         amount: u64,
         reason: EarnReason,
+        nonce: u64,
     ) -> Result<()> {
-        let cpi_accounts = MintTo {
-            mint: ctx.accounts.mint.to_account_info(),
-            to: ctx.accounts.player_token_account.to_account_info(),
-            authority: ctx.accounts.mint_authority.to_account_info(),
-        };
+        let config = &ctx.accounts.config;
+        require!(
+            amount <= config.earn_caps[reason.index()],
+            ErrorCode::EarnCapExceeded
+        );
+        require!(nonce > ctx.accounts.player_state.earn_nonce, ErrorCode::StaleNonce);
         
-        let seeds = &[
-            b"mint_authority",
-            ctx.accounts.mint.key().as_ref(),
-            &[ctx.accounts.mint_authority.bump],
-        ];
-        let signer = &[&seeds[..]];
+        // Rewards need the oracle's co-signature, or a voucher it signed in
+        // the preceding ed25519 instruction.
+        match &ctx.accounts.reward_oracle {
+            Some(oracle) => require_keys_eq!(
+                oracle.key(),
+                config.reward_oracle,
+                ErrorCode::Unauthorized
+            ),
+            None => {
+                require!(
+                    config.is_enabled(FEATURE_EARN_VOUCHERS),
+                    ErrorCode::FeatureDisabled
+                );
+                let message = earn_voucher_message(
+                    &ctx.accounts.player.key(),
+                    amount,
+                    &reason,
+                    nonce,
+                );
+                let signer =
+                    attestation::verify_ed25519_attestation(&ctx.accounts.instructions, &message)?;
+                require_keys_eq!(signer, config.reward_oracle, ErrorCode::Unauthorized);
+            }
+        }
+        
+        let now = Clock::get()?.unix_timestamp;
+        let token_data = &mut ctx.accounts.token_data;
+        token_data.record_mint(amount, now)?;
         
-        let cpi_ctx = CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            cpi_accounts,
-            signer,
-        );
-        token::mint_to(cpi_ctx, amount)?;
-
         let player_state = &mut ctx.accounts.player_state;
+        player_state.record_daily_earnings(amount, token_data.emission.player_daily_cap, now)?;
         player_state.tokens_earned += amount;
+        player_state.earn_nonce = nonce;
         
+        mint_zap(
+            &ctx.accounts.token_program,
+            &ctx.accounts.mint,
+            &ctx.accounts.player_token_account,
+            &ctx.accounts.mint_authority,
+            ctx.bumps.mint_authority,
+            amount,
+        )?;
+        
+        emit!(TokensEarned {
+            player: player_state.player,
+            amount,
+            reason: reason.clone(),
+            nonce,
+            daily_earned: player_state.daily_earned,
+        });
         msg!("Player earned {} $ZAP for {:?}", amount, reason);
         Ok(())
     }
     
+    pub fn initialize_global_leaderboard(ctx: Context<InitializeGlobalLeaderboard>) -> Result<()> {
+        leaderboard::initialize_global_leaderboard(ctx)
+    }
+    
+    pub fn record_match(ctx: Context<RecordMatch>, stats: MatchStats) -> Result<()> {
+        stats::record_match(ctx, stats)
+    }
+    
+    pub fn start_season(
+        ctx: Context<StartSeason>,
+        season_id: u32,
+        end_time: i64,
+        reward_brackets: Vec<RewardBracket>,
+    ) -> Result<()> {
+        season::start_season(ctx, season_id, end_time, reward_brackets)
+    }
+    
+    pub fn claim_season_reward(ctx: Context<ClaimSeasonReward>) -> Result<()> {
+        season::claim_season_reward(ctx)
+    }
+    
+    pub fn create_achievement(
+        ctx: Context<CreateAchievement>,
+        achievement_id: u32,
+        name: String,
+        criteria: AchievementCriteria,
+        zap_reward: u64,
+    ) -> Result<()> {
+        achievements::create_achievement(ctx, achievement_id, name, criteria, zap_reward)
+    }
+    
+    pub fn claim_achievement(ctx: Context<ClaimAchievement>) -> Result<()> {
+        achievements::claim_achievement(ctx)
+    }
+    
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_challenge(
+        ctx: Context<CreateChallenge>,
+        challenge_id: u32,
+        objective: ChallengeObjective,
+        target: u64,
+        reward: u64,
+        window: ChallengeWindow,
+        start_time: i64,
+        end_time: i64,
+    ) -> Result<()> {
+        challenges::create_challenge(
+            ctx,
+            challenge_id,
+            objective,
+            target,
+            reward,
+            window,
+            start_time,
+            end_time,
+        )
+    }
+    
+    pub fn complete_challenge(ctx: Context<CompleteChallenge>, window_index: u64) -> Result<()> {
+        challenges::complete_challenge(ctx, window_index)
+    }
+    
     // NFT functions
-    pub fn mint_weapon_nft(
-        ctx: Context<MintWeaponNFT>,
+    pub fn initialize_drop_table(
+        ctx: Context<InitializeDropTable>,
+        price: u64,
+        payment: PricePayment,
+        entries: Vec<DropEntry>,
+    ) -> Result<()> {
+        drops::initialize_drop_table(ctx, price, payment, entries)
+    }
+    
+    pub fn set_drop_table(
+        ctx: Context<UpdateDropTable>,
+        price: u64,
+        payment: PricePayment,
+        entries: Vec<DropEntry>,
+    ) -> Result<()> {
+        drops::set_drop_table(ctx, price, payment, entries)
+    }
+    
+    pub fn initialize_mystery_box(
+        ctx: Context<InitializeMysteryBox>,
+        price: u64,
+        payment: PricePayment,
+        entries: Vec<DropEntry>,
+    ) -> Result<()> {
+        mystery_box::initialize_mystery_box(ctx, price, payment, entries)
+    }
+    
+    pub fn set_mystery_box(
+        ctx: Context<UpdateMysteryBox>,
+        price: u64,
+        payment: PricePayment,
+        entries: Vec<DropEntry>,
+    ) -> Result<()> {
+        mystery_box::set_mystery_box(ctx, price, payment, entries)
+    }
+    
+    pub fn open_mystery_box(ctx: Context<OpenMysteryBox>) -> Result<()> {
+        mystery_box::open_mystery_box(ctx)
+    }
+    
+    pub fn request_weapon_mint(ctx: Context<RequestWeaponMint>) -> Result<()> {
+        nft::request_weapon_mint(ctx)
+    }
+    
+    pub fn mint_weapon_nft(ctx: Context<MintWeaponNFT>) -> Result<()> {
+        nft::mint_weapon_nft(ctx)
+    }
+    
+    pub fn sync_weapon_owner(ctx: Context<SyncWeaponOwner>) -> Result<()> {
+        nft::sync_weapon_owner(ctx)
+    }
+    
+    pub fn initialize_weapon_levels(
+        ctx: Context<InitializeWeaponLevels>,
+        xp_thresholds: Vec<u64>,
+        damage_growth_bps: u16,
+    ) -> Result<()> {
+        leveling::initialize_weapon_levels(ctx, xp_thresholds, damage_growth_bps)
+    }
+    
+    pub fn set_weapon_levels(
+        ctx: Context<UpdateWeaponLevels>,
+        xp_thresholds: Vec<u64>,
+        damage_growth_bps: u16,
+    ) -> Result<()> {
+        leveling::set_weapon_levels(ctx, xp_thresholds, damage_growth_bps)
+    }
+    
+    pub fn record_weapon_kills(ctx: Context<RecordWeaponKills>, kills: u64, xp: u64) -> Result<()> {
+        leveling::record_weapon_kills(ctx, kills, xp)
+    }
+    
+    pub fn initialize_weapon_supply(ctx: Context<InitializeWeaponSupply>) -> Result<()> {
+        supply::initialize_weapon_supply(ctx)
+    }
+    
+    pub fn set_supply_cap(
+        ctx: Context<UpdateWeaponSupply>,
         weapon_type: WeaponType,
         rarity: WeaponRarity,
+        cap: u32,
     ) -> Result<()> {
-        nft::mint_weapon_nft(ctx, weapon_type, rarity)
+        supply::set_supply_cap(ctx, weapon_type, rarity, cap)
+    }
+    
+    pub fn close_expired_mint_request(ctx: Context<CloseMintRequest>) -> Result<()> {
+        nft::close_expired_mint_request(ctx)
+    }
+    
+    pub fn refund_sold_out_mint_request(ctx: Context<RefundSoldOutMintRequest>) -> Result<()> {
+        nft::refund_sold_out_mint_request(ctx)
     }
     
     pub fn pack_a_punch_weapon(ctx: Context<PackAPunchWeapon>) -> Result<()> {
@@ -134,28 +525,56 @@ pub mod zombie_arena_protocol {
     pub fn create_tournament(
         ctx: Context<CreateTournament>,
         entry_fee: u64,
+        min_players: u32,
         max_players: u32,
         end_time: i64,
+        prize_schedule: PrizeSchedule,
     ) -> Result<()> {
-        tournament::create_tournament(ctx, entry_fee, max_players, end_time)
+        tournament::create_tournament(
+            ctx,
+            entry_fee,
+            min_players,
+            max_players,
+            end_time,
+            prize_schedule,
+        )
     }
     
     pub fn join_tournament(ctx: Context<JoinTournament>) -> Result<()> {
         tournament::join_tournament(ctx)
     }
     
+    pub fn start_tournament(ctx: Context<StartTournament>) -> Result<()> {
+        tournament::start_tournament(ctx)
+    }
+    
+    pub fn end_tournament(ctx: Context<EndTournament>) -> Result<()> {
+        tournament::end_tournament(ctx)
+    }
+    
+    pub fn cancel_tournament(ctx: Context<CancelTournament>) -> Result<()> {
+        tournament::cancel_tournament(ctx)
+    }
+    
     pub fn update_tournament_score(
         ctx: Context<UpdateTournamentScore>,
         round: u32,
         kills: u64,
+        nonce: u64,
     ) -> Result<()> {
-        tournament::update_tournament_score(ctx, round, kills)
+        tournament::update_tournament_score(ctx, round, kills, nonce)
     }
     
-    pub fn distribute_prizes(ctx: Context<DistributePrizes>) -> Result<()> {
+    pub fn distribute_prizes<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DistributePrizes<'info>>,
+    ) -> Result<()> {
         tournament::distribute_prizes(ctx)
     }
     
+    pub fn claim_refund(ctx: Context<ClaimRefund>) -> Result<()> {
+        tournament::claim_refund(ctx)
+    }
+    
     pub fn create_guild(
         ctx: Context<CreateGuild>,
         name: String,
@@ -165,12 +584,87 @@ pub mod zombie_arena_protocol {
     }
 }
 
+/// Mints $ZAP signed by the `mint_authority` PDA. Callers are responsible for
+/// checking the mint against the emission schedule first.
+pub fn mint_zap<'info>(
+    token_program: &Program<'info, Token>,
+    mint: &Account<'info, Mint>,
+    to: &Account<'info, TokenAccount>,
+    mint_authority: &AccountInfo<'info>,
+    mint_authority_bump: u8,
+    amount: u64,
+) -> Result<()> {
+    let mint_key = mint.key();
+    let seeds = &[
+        seeds::MINT_AUTHORITY,
+        mint_key.as_ref(),
+        &[mint_authority_bump],
+    ];
+    let signer = &[&seeds[..]];
+    
+    let cpi_accounts = MintTo {
+        mint: mint.to_account_info(),
+        to: to.to_account_info(),
+        authority: mint_authority.clone(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer);
+    token::mint_to(cpi_ctx, amount)?;
+    
+    emit!(TokensMinted {
+        mint: mint_key,
+        to: to.key(),
+        amount,
+    });
+    Ok(())
+}
+
+/// Burns $ZAP from `from` and adds it to `token_data.total_burned`. Pass
+/// `signer_seeds` when `authority` is a PDA, empty when it signed the
+/// transaction.
+#[allow(clippy::too_many_arguments)]
+pub fn burn_zap<'info>(
+    token_program: &Program<'info, Token>,
+    mint: &Account<'info, Mint>,
+    from: &Account<'info, TokenAccount>,
+    authority: &AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+    token_data: &mut Account<'info, TokenData>,
+    player: Pubkey,
+    amount: u64,
+) -> Result<()> {
+    let cpi_accounts = Burn {
+        mint: mint.to_account_info(),
+        from: from.to_account_info(),
+        authority: authority.clone(),
+    };
+    let cpi_ctx =
+        CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer_seeds);
+    token::burn(cpi_ctx, amount)?;
+    
+    token_data.total_burned += amount;
+    
+    emit!(TokensBurned {
+        player,
+        mint: mint.key(),
+        amount,
+        total_burned: token_data.total_burned,
+    });
+    Ok(())
+}
+
 #[derive(Accounts)]
-#[instruction(initial_supply: u64, decimals: u8)]
+#[instruction(initial_supply: u64, decimals: u8, emission: EmissionSchedule)]
 pub struct InitializeToken<'info> {
     #[account(mut)]
     pub admin: Signer<'info>,
     
+    #[account(
+        seeds = [seeds::CONFIG],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
     #[account(
         init,
         payer = admin,
@@ -183,7 +677,7 @@ pub struct InitializeToken<'info> {
         init,
         payer = admin,
         space = 8 + TokenData::INIT_SPACE,
-        seeds = [b"token_data", mint.key().as_ref()],
+        seeds = [seeds::TOKEN_DATA, mint.key().as_ref()],
         bump,
     )]
     pub token_data: Account<'info, TokenData>,
@@ -192,7 +686,7 @@ pub struct InitializeToken<'info> {
         init,
         payer = admin,
         space = 8 + 32,
-        seeds = [b"mint_authority", mint.key().as_ref()],
+        seeds = [seeds::MINT_AUTHORITY, mint.key().as_ref()],
         bump,
     )]
     /// CHECK: PDA used as mint authority
@@ -216,9 +710,18 @@ pub struct MintTokens<'info> {
     #[account(mut)]
     pub admin: Signer<'info>,
     
+    #[account(
+        seeds = [seeds::CONFIG],
+        bump = config.bump,
+        constraint = !config.is_paused(PAUSE_TOKEN) @ ErrorCode::ProgramPaused,
+        has_one = admin @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
     #[account(
         mut,
-        constraint = token_data.admin == admin.key() @ ErrorCode::Unauthorized,
+        seeds = [seeds::TOKEN_DATA, mint.key().as_ref()],
+        bump = token_data.bump,
     )]
     pub token_data: Account<'info, TokenData>,
     
@@ -226,7 +729,7 @@ pub struct MintTokens<'info> {
     pub mint: Account<'info, Mint>,
     
     #[account(
-        seeds = [b"mint_authority", mint.key().as_ref()],
+        seeds = [seeds::MINT_AUTHORITY, mint.key().as_ref()],
         bump,
     )]
     /// CHECK: PDA used as mint authority
@@ -235,6 +738,15 @@ pub struct MintTokens<'info> {
     #[account(mut)]
     pub token_account: Account<'info, TokenAccount>,
     
+    #[account(
+        mut,
+        seeds = [seeds::PLAYER_STATE, token_account.owner.as_ref()],
+        bump,
+    )]
+    /// CHECK: The token account owner's player state. When it exists the mint
+    /// counts toward their daily earning cap, see `mint_tokens`.
+    pub recipient_state: UncheckedAccount<'info>,
+    
     pub token_program: Program<'info, Token>,
 }
 
@@ -243,14 +755,31 @@ pub struct BurnForPerk<'info> {
     #[account(mut)]
     pub player: Signer<'info>,
     
+    #[account(
+        seeds = [seeds::CONFIG],
+        bump = config.bump,
+        constraint = !config.is_paused(PAUSE_TOKEN) @ ErrorCode::ProgramPaused,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
     #[account(
         mut,
-        seeds = [b"player_state", player.key().as_ref()],
+        seeds = [seeds::PLAYER_STATE, player.key().as_ref()],
         bump = player_state.bump,
     )]
     pub player_state: Account<'info, PlayerState>,
     
-    #[account(mut)]
+    #[account(
+        seeds = [seeds::PERK_TABLE],
+        bump = perk_table.bump,
+    )]
+    pub perk_table: Account<'info, PerkTable>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::TOKEN_DATA, mint.key().as_ref()],
+        bump = token_data.bump,
+    )]
     pub token_data: Account<'info, TokenData>,
     
     #[account(mut)]
@@ -259,22 +788,55 @@ pub struct BurnForPerk<'info> {
     #[account(
         mut,
         constraint = player_token_account.owner == player.key(),
+        constraint = player_token_account.mint == mint.key(),
     )]
     pub player_token_account: Account<'info, TokenAccount>,
     
     pub token_program: Program<'info, Token>,
 }
 
+#[derive(Accounts)]
+pub struct DeactivatePerk<'info> {
+    pub player: Signer<'info>,
+    
+    #[account(
+        seeds = [seeds::CONFIG],
+        bump = config.bump,
+        constraint = !config.is_paused(PAUSE_TOKEN) @ ErrorCode::ProgramPaused,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        seeds = [seeds::PERK_TABLE],
+        bump = perk_table.bump,
+    )]
+    pub perk_table: Account<'info, PerkTable>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::PLAYER_STATE, player.key().as_ref()],
+        bump = player_state.bump,
+    )]
+    pub player_state: Account<'info, PlayerState>,
+}
+
 #[derive(Accounts)]
 pub struct CreatePlayerState<'info> {
     #[account(mut)]
     pub player: Signer<'info>,
     
+    #[account(
+        seeds = [seeds::CONFIG],
+        bump = config.bump,
+        constraint = !config.is_paused(PAUSE_TOKEN) @ ErrorCode::ProgramPaused,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
     #[account(
         init,
         payer = player,
         space = 8 + PlayerState::INIT_SPACE,
-        seeds = [b"player_state", player.key().as_ref()],
+        seeds = [seeds::PLAYER_STATE, player.key().as_ref()],
         bump,
     )]
     pub player_state: Account<'info, PlayerState>,
@@ -289,16 +851,33 @@ pub struct EarnTokens<'info> {
     
     #[account(
         mut,
-        seeds = [b"player_state", player.key().as_ref()],
+        seeds = [seeds::PLAYER_STATE, player.key().as_ref()],
         bump = player_state.bump,
     )]
     pub player_state: Account<'info, PlayerState>,
     
+    #[account(
+        seeds = [seeds::CONFIG],
+        bump = config.bump,
+        constraint = !config.is_paused(PAUSE_TOKEN) @ ErrorCode::ProgramPaused,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    /// Reward oracle co-signing the payout. Leave out to use a signed voucher.
+    pub reward_oracle: Option<Signer<'info>>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::TOKEN_DATA, mint.key().as_ref()],
+        bump = token_data.bump,
+    )]
+    pub token_data: Account<'info, TokenData>,
+    
     #[account(mut)]
     pub mint: Account<'info, Mint>,
     
     #[account(
-        seeds = [b"mint_authority", mint.key().as_ref()],
+        seeds = [seeds::MINT_AUTHORITY, mint.key().as_ref()],
         bump,
     )]
     /// CHECK: PDA used as mint authority
@@ -307,48 +886,230 @@ pub struct EarnTokens<'info> {
     #[account(
         mut,
         constraint = player_token_account.owner == player.key(),
+        constraint = player_token_account.mint == mint.key(),
     )]
     pub player_token_account: Account<'info, TokenAccount>,
     
+    #[account(address = sysvar::instructions::ID)]
+    /// CHECK: Instructions sysvar, read for the ed25519 voucher
+    pub instructions: UncheckedAccount<'info>,
+    
     pub token_program: Program<'info, Token>,
 }
 
 #[account]
 #[derive(InitSpace)]
 pub struct TokenData {
-    pub admin: Pubkey,
     pub mint: Pubkey,
     pub total_burned: u64,
+    pub total_minted: u64,
+    pub emission: EmissionSchedule,
+    pub genesis_time: i64,
+    pub current_epoch: u64,
+    pub epoch_minted: u64,
     pub bump: u8,
 }
 
+impl TokenData {
+    pub fn epoch_at(&self, now: i64) -> u64 {
+        ((now - self.genesis_time).max(0) / self.emission.epoch_duration) as u64
+    }
+    
+    /// Emission budget for `epoch`, halved every `halving_interval` epochs.
+    pub fn epoch_budget(&self, epoch: u64) -> u64 {
+        let halvings = epoch / self.emission.halving_interval;
+        if halvings >= u64::BITS as u64 {
+            return 0;
+        }
+        self.emission.initial_epoch_emission >> halvings
+    }
+    
+    /// Checks a mint against the max supply and the current epoch's budget,
+    /// then books it.
+    pub fn record_mint(&mut self, amount: u64, now: i64) -> Result<()> {
+        let total_minted = self
+            .total_minted
+            .checked_add(amount)
+            .filter(|total| *total <= self.emission.max_supply)
+            .ok_or(ErrorCode::MaxSupplyExceeded)?;
+        
+        let epoch = self.epoch_at(now);
+        if epoch != self.current_epoch {
+            self.current_epoch = epoch;
+            self.epoch_minted = 0;
+        }
+        let epoch_minted = self
+            .epoch_minted
+            .checked_add(amount)
+            .filter(|minted| *minted <= self.epoch_budget(epoch))
+            .ok_or(ErrorCode::EpochBudgetExceeded)?;
+        
+        self.total_minted = total_minted;
+        self.epoch_minted = epoch_minted;
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, InitSpace)]
+pub struct EmissionSchedule {
+    /// Most $ZAP that can ever be minted through the program.
+    pub max_supply: u64,
+    /// Length of an emission epoch, in seconds.
+    pub epoch_duration: i64,
+    /// Mint budget for the first epochs, before any halving.
+    pub initial_epoch_emission: u64,
+    /// Number of epochs between each halving of the budget.
+    pub halving_interval: u64,
+    /// Most $ZAP a single player can receive per day.
+    pub player_daily_cap: u64,
+}
+
+impl EmissionSchedule {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.epoch_duration > 0 && self.halving_interval > 0,
+            ErrorCode::InvalidEmissionSchedule
+        );
+        require!(
+            self.initial_epoch_emission <= self.max_supply,
+            ErrorCode::InvalidEmissionSchedule
+        );
+        Ok(())
+    }
+}
+
 #[account]
 #[derive(InitSpace)]
 pub struct PlayerState {
     pub player: Pubkey,
     pub total_kills: u64,
     pub highest_round: u32,
+    pub total_headshots: u64,
+    pub total_revives: u64,
+    pub total_rounds_survived: u64,
+    /// Seconds played across all recorded matches.
+    pub total_play_time: u64,
+    pub matches_played: u64,
+    /// Totals for the season in `season_stats.season`.
+    pub season_stats: SeasonStats,
+    /// The season record before that, kept so its reward can still be claimed.
+    pub previous_season_stats: SeasonStats,
     pub tokens_earned: u64,
-    #[max_len(10)]
-    pub active_perks: Vec<PerkType>,
+    /// Last reward nonce accepted by `earn_tokens`.
+    pub earn_nonce: u64,
+    /// Day (unix time / `SECONDS_PER_DAY`) that `daily_earned` refers to.
+    pub earn_day: i64,
+    pub daily_earned: u64,
+    #[max_len(MAX_PERK_SLOTS)]
+    pub active_perks: Vec<ActivePerk>,
+    /// Revives granted by `ReviveCredits` perks, spent by `consume_revive`.
+    /// Lost when the perk that granted them expires or is deactivated.
+    pub revive_credits: u8,
     pub bump: u8,
 }
 
 impl PlayerState {
-    pub fn activate_perk(&mut self, perk: PerkType) -> Result<()> {
-        if self.active_perks.len() >= 4 {
+    pub fn record_daily_earnings(&mut self, amount: u64, daily_cap: u64, now: i64) -> Result<()> {
+        let day = now / SECONDS_PER_DAY;
+        if day != self.earn_day {
+            self.earn_day = day;
+            self.daily_earned = 0;
+        }
+        
+        self.daily_earned = self
+            .daily_earned
+            .checked_add(amount)
+            .filter(|earned| *earned <= daily_cap)
+            .ok_or(ErrorCode::DailyEarnCapExceeded)?;
+        Ok(())
+    }
+    
+    /// Drops perks whose duration has run out. Called lazily whenever the
+    /// perk list is about to be read or changed. Losing a perk that gave
+    /// extra slots, like Mule Kick, also drops the newest perks that no
+    /// longer fit.
+    pub fn prune_expired_perks(&mut self, now: i64, perk_table: &PerkTable) {
+        self.active_perks.retain(|active| active.expires_at() > now);
+        self.drop_perks_over_slots(perk_table);
+    }
+    
+    /// Drops the newest perks beyond the slot count, then any revive credits
+    /// no longer backed by an active `ReviveCredits` perk.
+    fn drop_perks_over_slots(&mut self, perk_table: &PerkTable) {
+        while self.active_perks.len() > perk_table.perk_slots(&self.active_perks) {
+            self.active_perks.pop();
+        }
+        
+        let grants_revives = self.active_perks.iter().any(|active| {
+            matches!(perk_table.effect(&active.perk), PerkEffect::ReviveCredits(_))
+        });
+        if !grants_revives {
+            self.revive_credits = 0;
+        }
+    }
+    
+    pub fn activate_perk(
+        &mut self,
+        perk: PerkType,
+        now: i64,
+        duration: i64,
+        perk_table: &PerkTable,
+    ) -> Result<()> {
+        self.prune_expired_perks(now, perk_table);
+        
+        if self.active_perks.len() >= perk_table.perk_slots(&self.active_perks) {
             return Err(ErrorCode::MaxPerksReached.into());
         }
         
-        if self.active_perks.contains(&perk) {
+        if self.active_perks.iter().any(|active| active.perk == perk) {
             return Err(ErrorCode::PerkAlreadyActive.into());
         }
         
-        self.active_perks.push(perk);
+        if let PerkEffect::ReviveCredits(credits) = perk_table.effect(&perk) {
+            self.revive_credits = self.revive_credits.saturating_add(credits);
+        }
+        
+        self.active_perks.push(ActivePerk {
+            perk,
+            activated_at: now,
+            duration,
+        });
+        Ok(())
+    }
+    
+    pub fn deactivate_perk(
+        &mut self,
+        perk: &PerkType,
+        now: i64,
+        perk_table: &PerkTable,
+    ) -> Result<()> {
+        self.prune_expired_perks(now, perk_table);
+        
+        let position = self
+            .active_perks
+            .iter()
+            .position(|active| active.perk == *perk)
+            .ok_or(ErrorCode::PerkNotActive)?;
+        self.active_perks.remove(position);
+        self.drop_perks_over_slots(perk_table);
         Ok(())
     }
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, InitSpace)]
+pub struct ActivePerk {
+    pub perk: PerkType,
+    pub activated_at: i64,
+    /// Seconds the perk stays active for.
+    pub duration: i64,
+}
+
+impl ActivePerk {
+    pub fn expires_at(&self) -> i64 {
+        self.activated_at.saturating_add(self.duration)
+    }
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, InitSpace)]
 pub enum PerkType {
     Juggernog,
@@ -361,6 +1122,23 @@ pub enum PerkType {
     MuleKick,
 }
 
+impl PerkType {
+    pub const COUNT: usize = 8;
+    
+    pub fn index(&self) -> usize {
+        match self {
+            PerkType::Juggernog => 0,
+            PerkType::SpeedCola => 1,
+            PerkType::DoubleTap => 2,
+            PerkType::QuickRevive => 3,
+            PerkType::StaminUp => 4,
+            PerkType::PHDFlopper => 5,
+            PerkType::Deadshot => 6,
+            PerkType::MuleKick => 7,
+        }
+    }
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, InitSpace)]
 pub enum EarnReason {
     RoundSurvival,
@@ -371,6 +1149,40 @@ pub enum EarnReason {
     Challenge,
 }
 
+impl EarnReason {
+    pub const COUNT: usize = 6;
+    
+    pub fn index(&self) -> usize {
+        match self {
+            EarnReason::RoundSurvival => 0,
+            EarnReason::ZombieKill => 1,
+            EarnReason::Headshot => 2,
+            EarnReason::Assist => 3,
+            EarnReason::Revive => 4,
+            EarnReason::Challenge => 5,
+        }
+    }
+}
+
+/// Domain tag prefixed to reward vouchers signed by the reward oracle.
+pub const EARN_VOUCHER_TAG: &[u8] = b"zap:earn_voucher";
+
+/// Bytes the reward oracle signs to authorize an `earn_tokens` payout.
+pub fn earn_voucher_message(
+    player: &Pubkey,
+    amount: u64,
+    reason: &EarnReason,
+    nonce: u64,
+) -> Vec<u8> {
+    let mut message = Vec::with_capacity(EARN_VOUCHER_TAG.len() + 32 + 8 + 1 + 8);
+    message.extend_from_slice(EARN_VOUCHER_TAG);
+    message.extend_from_slice(player.as_ref());
+    message.extend_from_slice(&amount.to_le_bytes());
+    message.push(reason.index() as u8);
+    message.extend_from_slice(&nonce.to_le_bytes());
+    message
+}
+
 #[error_code]
 pub enum ErrorCode {
     #[msg("Unauthorized")]
@@ -381,4 +1193,381 @@ pub enum ErrorCode {
     PerkAlreadyActive,
     #[msg("Weapon already Pack-a-Punched")]
     AlreadyPackAPunched,
-}
\ No newline at end of file
+    #[msg("Tournament end time must be in the future")]
+    InvalidTournamentTime,
+    #[msg("Invalid tournament status for this action")]
+    InvalidTournamentStatus,
+    #[msg("Tournament has already passed its end time")]
+    TournamentOver,
+    #[msg("Tournament has not reached its end time")]
+    TournamentNotOver,
+    #[msg("No players on the tournament leaderboard")]
+    NoRankedPlayers,
+    #[msg("Missing token account for a ranked winner")]
+    MissingWinnerAccount,
+    #[msg("Token account does not belong to the ranked winner")]
+    InvalidWinnerAccount,
+    #[msg("Prize schedule must be descending and add up to 10,000 basis points")]
+    InvalidPrizeSchedule,
+    #[msg("Minimum players must be at least 1 and no more than max players")]
+    InvalidPlayerLimits,
+    #[msg("Tournament has not reached its minimum player count")]
+    NotEnoughPlayers,
+    #[msg("Refunds are only available for cancelled or under-filled tournaments")]
+    RefundNotAvailable,
+    #[msg("Missing ed25519 attestation instruction")]
+    MissingAttestation,
+    #[msg("Attestation does not match the expected signer or message")]
+    InvalidAttestation,
+    #[msg("Nonce has already been used")]
+    StaleNonce,
+    #[msg("Amount exceeds the cap for this earn reason")]
+    EarnCapExceeded,
+    #[msg("Emission schedule needs a non-zero epoch length and halving interval")]
+    InvalidEmissionSchedule,
+    #[msg("Mint would exceed the $ZAP max supply")]
+    MaxSupplyExceeded,
+    #[msg("Mint would exceed this epoch's emission budget")]
+    EpochBudgetExceeded,
+    #[msg("Mint would exceed the player's daily earning cap")]
+    DailyEarnCapExceeded,
+    #[msg("Game server is already registered")]
+    GameServerAlreadyRegistered,
+    #[msg("Game server is not registered")]
+    GameServerNotRegistered,
+    #[msg("Too many game servers registered")]
+    TooManyGameServers,
+    #[msg("This feature is disabled")]
+    FeatureDisabled,
+    #[msg("This part of the program is paused")]
+    ProgramPaused,
+    #[msg("Multisig owners must be unique and between 1 and 10")]
+    InvalidMultisigOwners,
+    #[msg("Threshold must be between 1 and the number of owners")]
+    InvalidThreshold,
+    #[msg("Timelock must be between zero and 30 days")]
+    InvalidTimelock,
+    #[msg("Signer is not a multisig owner")]
+    NotMultisigOwner,
+    #[msg("Proposal has already been executed")]
+    ProposalAlreadyExecuted,
+    #[msg("Proposal has not reached its approval threshold")]
+    ProposalNotApproved,
+    #[msg("Proposal timelock has not elapsed")]
+    TimelockNotElapsed,
+    #[msg("Proposal was created under a previous owner set")]
+    StaleProposal,
+    #[msg("Perk is not active")]
+    PerkNotActive,
+    #[msg("Perk has no price set and cannot be bought")]
+    PerkNotForSale,
+    #[msg("Player has no revive credits")]
+    NoReviveCredits,
+    #[msg("New season must come after the current one")]
+    InvalidSeason,
+    #[msg("Season must end in the future")]
+    InvalidSeasonTime,
+    #[msg("Reward brackets must be non-empty, at most 10, with strictly descending scores")]
+    InvalidRewardBrackets,
+    #[msg("Season has not ended yet")]
+    SeasonNotOver,
+    #[msg("No season reward for this player")]
+    NoSeasonReward,
+    #[msg("Season reward pool cannot cover this reward")]
+    SeasonPoolDepleted,
+    #[msg("Achievement name must be 1 to 32 bytes")]
+    InvalidAchievementName,
+    #[msg("Player does not meet the achievement criteria")]
+    AchievementNotEarned,
+    #[msg("Challenge needs a non-zero target and must end after it starts")]
+    InvalidChallenge,
+    #[msg("Challenge is not running in this window")]
+    ChallengeNotActive,
+    #[msg("Drop table must have 1 to 32 entries, all with a non-zero weight")]
+    InvalidDropTable,
+    #[msg("SlotHashes sysvar data is malformed")]
+    InvalidSlotHashes,
+    #[msg("Mint request cannot be revealed until a later slot")]
+    RevealNotReady,
+    #[msg("Mint request is too old to reveal")]
+    RevealExpired,
+    #[msg("Mint request can still be revealed")]
+    MintRequestNotExpired,
+    #[msg("Fee recipient token account is required for this price")]
+    MissingFeeRecipientAccount,
+    #[msg("Weapon supply cap reached")]
+    SupplyCapReached,
+    #[msg("Supply cap is below the number already minted")]
+    InvalidSupplyCap,
+    #[msg("Token account does not hold this weapon for the player")]
+    NotWeaponHolder,
+    #[msg("XP thresholds must be strictly increasing, at most 29 of them")]
+    InvalidWeaponLevels,
+    #[msg("Rolled weapon is not sold out")]
+    RollNotSoldOut,
+    #[msg("Weapon achievements can't carry a $ZAP reward")]
+    WeaponAchievementReward,
+    #[msg("Missing or mismatched $ZAP accounts for the achievement reward")]
+    InvalidRewardAccounts,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    const DAY: i64 = 86_400;
+    
+    fn token_data() -> TokenData {
+        TokenData {
+            mint: Pubkey::new_unique(),
+            total_burned: 0,
+            total_minted: 0,
+            emission: EmissionSchedule {
+                max_supply: 10_000,
+                epoch_duration: DAY,
+                initial_epoch_emission: 1_000,
+                halving_interval: 2,
+                player_daily_cap: 100,
+            },
+            genesis_time: 1_000,
+            current_epoch: 0,
+            epoch_minted: 0,
+            bump: 0,
+        }
+    }
+    
+    #[test]
+    fn epoch_budget_halves_every_interval() {
+        let token_data = token_data();
+        assert_eq!(token_data.epoch_budget(0), 1_000);
+        assert_eq!(token_data.epoch_budget(1), 1_000);
+        assert_eq!(token_data.epoch_budget(2), 500);
+        assert_eq!(token_data.epoch_budget(5), 250);
+        assert_eq!(token_data.epoch_budget(2 * 63), 0);
+        assert_eq!(token_data.epoch_budget(2 * 64), 0);
+        assert_eq!(token_data.epoch_budget(u64::MAX), 0);
+    }
+    
+    #[test]
+    fn epoch_at_counts_from_genesis() {
+        let token_data = token_data();
+        assert_eq!(token_data.epoch_at(0), 0);
+        assert_eq!(token_data.epoch_at(1_000 + DAY - 1), 0);
+        assert_eq!(token_data.epoch_at(1_000 + DAY), 1);
+    }
+    
+    #[test]
+    fn record_mint_enforces_the_epoch_budget() {
+        let mut token_data = token_data();
+        token_data.record_mint(600, 1_000).unwrap();
+        token_data.record_mint(400, 1_000).unwrap();
+        assert_eq!(
+            token_data.record_mint(1, 1_000).unwrap_err(),
+            ErrorCode::EpochBudgetExceeded.into()
+        );
+        assert_eq!(token_data.total_minted, 1_000);
+        assert_eq!(token_data.epoch_minted, 1_000);
+    }
+    
+    #[test]
+    fn record_mint_resets_the_budget_each_epoch() {
+        let mut token_data = token_data();
+        token_data.record_mint(1_000, 1_000).unwrap();
+        token_data.record_mint(1_000, 1_000 + DAY).unwrap();
+        assert_eq!(token_data.current_epoch, 1);
+        
+        // Epoch 2 is the first halving.
+        assert_eq!(
+            token_data.record_mint(501, 1_000 + 2 * DAY).unwrap_err(),
+            ErrorCode::EpochBudgetExceeded.into()
+        );
+        token_data.record_mint(500, 1_000 + 2 * DAY).unwrap();
+        assert_eq!(token_data.total_minted, 2_500);
+    }
+    
+    #[test]
+    fn record_mint_enforces_the_max_supply() {
+        let mut token_data = token_data();
+        token_data.total_minted = 9_900;
+        assert_eq!(
+            token_data.record_mint(101, 1_000).unwrap_err(),
+            ErrorCode::MaxSupplyExceeded.into()
+        );
+        assert_eq!(
+            token_data.record_mint(u64::MAX, 1_000).unwrap_err(),
+            ErrorCode::MaxSupplyExceeded.into()
+        );
+        token_data.record_mint(100, 1_000).unwrap();
+        assert_eq!(token_data.total_minted, 10_000);
+    }
+    
+    fn perk_table() -> PerkTable {
+        let mut effects = [PerkEffect::None; PerkType::COUNT];
+        effects[PerkType::MuleKick.index()] = PerkEffect::ExtraPerkSlots(2);
+        effects[PerkType::QuickRevive.index()] = PerkEffect::ReviveCredits(1);
+        PerkTable {
+            prices: [1; PerkType::COUNT],
+            effects,
+            bump: 0,
+        }
+    }
+    
+    pub(crate) fn player_state() -> PlayerState {
+        PlayerState {
+            player: Pubkey::new_unique(),
+            total_kills: 0,
+            highest_round: 0,
+            total_headshots: 0,
+            total_revives: 0,
+            total_rounds_survived: 0,
+            total_play_time: 0,
+            matches_played: 0,
+            season_stats: SeasonStats::default(),
+            previous_season_stats: SeasonStats::default(),
+            tokens_earned: 0,
+            earn_nonce: 0,
+            earn_day: 0,
+            daily_earned: 0,
+            active_perks: vec![],
+            revive_credits: 0,
+            bump: 0,
+        }
+    }
+    
+    fn perks(player_state: &PlayerState) -> Vec<PerkType> {
+        player_state
+            .active_perks
+            .iter()
+            .map(|active| active.perk.clone())
+            .collect()
+    }
+    
+    /// Mule Kick, worth two extra slots, plus five more perks: the last one
+    /// only fits thanks to Mule Kick. Mule Kick runs out first, at 50.
+    fn player_with_mule_kick(perk_table: &PerkTable) -> PlayerState {
+        let mut player_state = player_state();
+        player_state
+            .activate_perk(PerkType::MuleKick, 0, 50, perk_table)
+            .unwrap();
+        for perk in [
+            PerkType::Juggernog,
+            PerkType::SpeedCola,
+            PerkType::DoubleTap,
+            PerkType::StaminUp,
+            PerkType::Deadshot,
+        ] {
+            player_state.activate_perk(perk, 10, 100, perk_table).unwrap();
+        }
+        player_state
+    }
+    
+    #[test]
+    fn activate_perk_respects_the_slot_count() {
+        let perk_table = perk_table();
+        let mut player_state = player_with_mule_kick(&perk_table);
+        assert_eq!(
+            player_state
+                .activate_perk(PerkType::PHDFlopper, 10, 100, &perk_table)
+                .unwrap_err(),
+            ErrorCode::MaxPerksReached.into()
+        );
+    }
+    
+    #[test]
+    fn expired_mule_kick_drops_the_newest_perk() {
+        let perk_table = perk_table();
+        let mut player_state = player_with_mule_kick(&perk_table);
+        player_state.prune_expired_perks(50, &perk_table);
+        assert_eq!(
+            perks(&player_state),
+            vec![
+                PerkType::Juggernog,
+                PerkType::SpeedCola,
+                PerkType::DoubleTap,
+                PerkType::StaminUp,
+            ]
+        );
+    }
+    
+    #[test]
+    fn deactivated_mule_kick_drops_the_newest_perk() {
+        let perk_table = perk_table();
+        let mut player_state = player_with_mule_kick(&perk_table);
+        player_state
+            .deactivate_perk(&PerkType::MuleKick, 20, &perk_table)
+            .unwrap();
+        assert_eq!(
+            perks(&player_state),
+            vec![
+                PerkType::Juggernog,
+                PerkType::SpeedCola,
+                PerkType::DoubleTap,
+                PerkType::StaminUp,
+            ]
+        );
+    }
+    
+    #[test]
+    fn perks_within_the_base_slots_survive_losing_mule_kick() {
+        let perk_table = perk_table();
+        let mut player_state = player_state();
+        player_state
+            .activate_perk(PerkType::MuleKick, 0, 50, &perk_table)
+            .unwrap();
+        player_state
+            .activate_perk(PerkType::Juggernog, 0, 100, &perk_table)
+            .unwrap();
+        player_state.prune_expired_perks(50, &perk_table);
+        assert_eq!(perks(&player_state), vec![PerkType::Juggernog]);
+    }
+    
+    #[test]
+    fn revive_credits_go_with_quick_revive() {
+        let perk_table = perk_table();
+        let mut player_state = player_state();
+        player_state
+            .activate_perk(PerkType::QuickRevive, 0, 50, &perk_table)
+            .unwrap();
+        assert_eq!(player_state.revive_credits, 1);
+        
+        player_state.prune_expired_perks(49, &perk_table);
+        assert_eq!(player_state.revive_credits, 1);
+        player_state.prune_expired_perks(50, &perk_table);
+        assert_eq!(player_state.revive_credits, 0);
+        
+        // Re-buying grants a fresh credit rather than stacking on old ones.
+        player_state
+            .activate_perk(PerkType::QuickRevive, 60, 50, &perk_table)
+            .unwrap();
+        assert_eq!(player_state.revive_credits, 1);
+        player_state
+            .deactivate_perk(&PerkType::QuickRevive, 70, &perk_table)
+            .unwrap();
+        assert_eq!(player_state.revive_credits, 0);
+    }
+    
+    #[test]
+    fn revive_credits_go_when_quick_revive_loses_its_slot() {
+        let perk_table = perk_table();
+        let mut player_state = player_state();
+        player_state
+            .activate_perk(PerkType::MuleKick, 0, 50, &perk_table)
+            .unwrap();
+        for perk in [
+            PerkType::Juggernog,
+            PerkType::SpeedCola,
+            PerkType::DoubleTap,
+            PerkType::StaminUp,
+        ] {
+            player_state.activate_perk(perk, 0, 100, &perk_table).unwrap();
+        }
+        player_state
+            .activate_perk(PerkType::QuickRevive, 0, 100, &perk_table)
+            .unwrap();
+        assert_eq!(player_state.revive_credits, 1);
+        
+        player_state.prune_expired_perks(50, &perk_table);
+        assert!(!perks(&player_state).contains(&PerkType::QuickRevive));
+        assert_eq!(player_state.revive_credits, 0);
+    }
+}
@@ -0,0 +1,530 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{instruction::Instruction, program::invoke_signed};
+
+use crate::config::ProgramConfig;
+use crate::events::*;
+use crate::{seeds, ErrorCode, SECONDS_PER_DAY};
+
+pub const MAX_MULTISIG_OWNERS: usize = 10;
+
+/// Longest timelock the owners can set, so approved proposals always become
+/// executable in reasonable time.
+pub const MAX_MULTISIG_TIMELOCK: i64 = 30 * SECONDS_PER_DAY;
+
+// The multisig acts through the `multisig_signer` PDA. Once it has been made
+// `ProgramConfig.admin` (the current admin calls `propose_admin` with it and
+// the owners execute an `accept_admin` proposal), every admin-gated
+// instruction can only be reached through an approved proposal.
+
+#[derive(Accounts)]
+pub struct CreateMultisig<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        seeds = [seeds::CONFIG],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Multisig::INIT_SPACE,
+        seeds = [seeds::MULTISIG],
+        bump,
+    )]
+    pub multisig: Account<'info, Multisig>,
+    
+    #[account(
+        seeds = [seeds::MULTISIG_SIGNER],
+        bump,
+    )]
+    /// CHECK: PDA the multisig signs admin actions with
+    pub multisig_signer: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_multisig(
+    ctx: Context<CreateMultisig>,
+    owners: Vec<Pubkey>,
+    threshold: u8,
+    timelock: i64,
+) -> Result<()> {
+    Multisig::validate_owners(&owners, threshold)?;
+    Multisig::validate_timelock(timelock)?;
+    
+    let multisig = &mut ctx.accounts.multisig;
+    multisig.owners = owners;
+    multisig.threshold = threshold;
+    multisig.timelock = timelock;
+    multisig.proposal_count = 0;
+    multisig.owner_set_seqno = 0;
+    multisig.signer_bump = ctx.bumps.multisig_signer;
+    multisig.bump = ctx.bumps.multisig;
+    
+    emit!(MultisigCreated {
+        owners: multisig.owners.clone(),
+        threshold,
+        timelock,
+    });
+    msg!(
+        "Multisig created: {} of {}, signer {}",
+        threshold,
+        multisig.owners.len(),
+        ctx.accounts.multisig_signer.key()
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(program_id: Pubkey, accounts: Vec<ProposalAccount>, data: Vec<u8>)]
+pub struct CreateProposal<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::MULTISIG],
+        bump = multisig.bump,
+    )]
+    pub multisig: Account<'info, Multisig>,
+    
+    #[account(
+        init,
+        payer = proposer,
+        space = Proposal::space(accounts.len(), data.len(), multisig.owners.len()),
+        seeds = [seeds::PROPOSAL, multisig.key().as_ref(), &multisig.proposal_count.to_le_bytes()],
+        bump,
+    )]
+    pub proposal: Account<'info, Proposal>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_proposal(
+    ctx: Context<CreateProposal>,
+    program_id: Pubkey,
+    accounts: Vec<ProposalAccount>,
+    data: Vec<u8>,
+) -> Result<()> {
+    let multisig = &mut ctx.accounts.multisig;
+    let owner_index = multisig.owner_index(&ctx.accounts.proposer.key())?;
+    
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.multisig = multisig.key();
+    proposal.index = multisig.proposal_count;
+    proposal.proposer = ctx.accounts.proposer.key();
+    proposal.program_id = program_id;
+    proposal.accounts = accounts;
+    proposal.data = data;
+    proposal.approvals = vec![false; multisig.owners.len()];
+    proposal.approved_at = None;
+    proposal.executed = false;
+    proposal.owner_set_seqno = multisig.owner_set_seqno;
+    proposal.bump = ctx.bumps.proposal;
+    
+    // Proposing counts as the proposer's approval.
+    proposal.approve(owner_index, multisig.threshold, Clock::get()?.unix_timestamp);
+    
+    multisig.proposal_count += 1;
+    
+    emit!(ProposalCreated {
+        proposal: proposal.key(),
+        index: proposal.index,
+        proposer: proposal.proposer,
+    });
+    msg!("Proposal {} created by {}", proposal.index, proposal.proposer);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ApproveProposal<'info> {
+    pub owner: Signer<'info>,
+    
+    #[account(
+        seeds = [seeds::MULTISIG],
+        bump = multisig.bump,
+    )]
+    pub multisig: Account<'info, Multisig>,
+    
+    #[account(
+        mut,
+        has_one = multisig,
+        constraint = !proposal.executed @ ErrorCode::ProposalAlreadyExecuted,
+        constraint = proposal.owner_set_seqno == multisig.owner_set_seqno @ ErrorCode::StaleProposal,
+    )]
+    pub proposal: Account<'info, Proposal>,
+}
+
+pub fn approve_proposal(ctx: Context<ApproveProposal>) -> Result<()> {
+    let multisig = &ctx.accounts.multisig;
+    let owner_index = multisig.owner_index(&ctx.accounts.owner.key())?;
+    
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.approve(owner_index, multisig.threshold, Clock::get()?.unix_timestamp);
+    
+    emit!(ProposalApproved {
+        proposal: proposal.key(),
+        owner: ctx.accounts.owner.key(),
+        approvals: proposal.approval_count() as u32,
+        approved_at: proposal.approved_at,
+    });
+    msg!(
+        "Proposal {} approved by {} ({} of {})",
+        proposal.index,
+        ctx.accounts.owner.key(),
+        proposal.approval_count(),
+        multisig.threshold
+    );
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ExecuteProposal<'info> {
+    #[account(
+        seeds = [seeds::MULTISIG],
+        bump = multisig.bump,
+    )]
+    pub multisig: Account<'info, Multisig>,
+    
+    #[account(
+        mut,
+        has_one = multisig,
+        constraint = !proposal.executed @ ErrorCode::ProposalAlreadyExecuted,
+        constraint = proposal.owner_set_seqno == multisig.owner_set_seqno @ ErrorCode::StaleProposal,
+    )]
+    pub proposal: Account<'info, Proposal>,
+    
+    #[account(
+        seeds = [seeds::MULTISIG_SIGNER],
+        bump = multisig.signer_bump,
+    )]
+    /// CHECK: PDA the multisig signs admin actions with
+    pub multisig_signer: UncheckedAccount<'info>,
+    // remaining_accounts: every account the proposed instruction touches,
+    // plus the program being invoked.
+}
+
+pub fn execute_proposal<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ExecuteProposal<'info>>,
+) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        now >= proposal.executable_at(ctx.accounts.multisig.timelock)?,
+        ErrorCode::TimelockNotElapsed
+    );
+    
+    // Persist the flag before invoking so the proposal can't be replayed from
+    // inside its own instruction.
+    proposal.executed = true;
+    proposal.exit(&crate::ID)?;
+    
+    let ix = Instruction {
+        program_id: proposal.program_id,
+        accounts: proposal.accounts.iter().map(AccountMeta::from).collect(),
+        data: proposal.data.clone(),
+    };
+    
+    let seeds = &[seeds::MULTISIG_SIGNER, &[ctx.accounts.multisig.signer_bump]];
+    let signer = &[&seeds[..]];
+    invoke_signed(&ix, ctx.remaining_accounts, signer)?;
+    
+    emit!(ProposalExecuted {
+        proposal: proposal.key(),
+        index: proposal.index,
+    });
+    msg!("Proposal {} executed", proposal.index);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateMultisig<'info> {
+    #[account(
+        mut,
+        seeds = [seeds::MULTISIG],
+        bump = multisig.bump,
+    )]
+    pub multisig: Account<'info, Multisig>,
+    
+    #[account(
+        seeds = [seeds::MULTISIG_SIGNER],
+        bump = multisig.signer_bump,
+    )]
+    pub multisig_signer: Signer<'info>,
+}
+
+/// Replaces the owner set. Only reachable through an executed proposal, and
+/// invalidates every proposal still pending under the old owners.
+pub fn set_multisig_owners(
+    ctx: Context<UpdateMultisig>,
+    owners: Vec<Pubkey>,
+    threshold: u8,
+) -> Result<()> {
+    Multisig::validate_owners(&owners, threshold)?;
+    
+    let multisig = &mut ctx.accounts.multisig;
+    multisig.owners = owners;
+    multisig.threshold = threshold;
+    multisig.owner_set_seqno += 1;
+    
+    emit!(MultisigOwnersSet {
+        owners: multisig.owners.clone(),
+        threshold,
+    });
+    msg!("Multisig owners set: {} of {}", threshold, multisig.owners.len());
+    Ok(())
+}
+
+/// Changes the delay between a proposal reaching its threshold and being
+/// executable. Only reachable through an executed proposal.
+pub fn set_multisig_timelock(ctx: Context<UpdateMultisig>, timelock: i64) -> Result<()> {
+    Multisig::validate_timelock(timelock)?;
+    
+    let multisig = &mut ctx.accounts.multisig;
+    multisig.timelock = timelock;
+    
+    emit!(MultisigTimelockSet { timelock });
+    msg!("Multisig timelock set to {}s", timelock);
+    Ok(())
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Multisig {
+    #[max_len(MAX_MULTISIG_OWNERS)]
+    pub owners: Vec<Pubkey>,
+    pub threshold: u8,
+    /// Seconds a proposal must wait after reaching its threshold.
+    pub timelock: i64,
+    pub proposal_count: u64,
+    /// Bumped whenever the owners change, retiring older proposals.
+    pub owner_set_seqno: u32,
+    pub signer_bump: u8,
+    pub bump: u8,
+}
+
+impl Multisig {
+    pub fn validate_owners(owners: &[Pubkey], threshold: u8) -> Result<()> {
+        require!(
+            !owners.is_empty() && owners.len() <= MAX_MULTISIG_OWNERS,
+            ErrorCode::InvalidMultisigOwners
+        );
+        let has_duplicates = owners
+            .iter()
+            .enumerate()
+            .any(|(i, owner)| owners[..i].contains(owner));
+        require!(!has_duplicates, ErrorCode::InvalidMultisigOwners);
+        require!(
+            threshold >= 1 && threshold as usize <= owners.len(),
+            ErrorCode::InvalidThreshold
+        );
+        Ok(())
+    }
+    
+    pub fn validate_timelock(timelock: i64) -> Result<()> {
+        require!(
+            (0..=MAX_MULTISIG_TIMELOCK).contains(&timelock),
+            ErrorCode::InvalidTimelock
+        );
+        Ok(())
+    }
+    
+    pub fn owner_index(&self, key: &Pubkey) -> Result<usize> {
+        self.owners
+            .iter()
+            .position(|owner| owner == key)
+            .ok_or_else(|| ErrorCode::NotMultisigOwner.into())
+    }
+}
+
+#[account]
+pub struct Proposal {
+    pub multisig: Pubkey,
+    pub index: u64,
+    pub proposer: Pubkey,
+    pub program_id: Pubkey,
+    pub accounts: Vec<ProposalAccount>,
+    pub data: Vec<u8>,
+    /// One flag per multisig owner, in owner order.
+    pub approvals: Vec<bool>,
+    /// When the proposal reached its threshold; the timelock runs from here.
+    pub approved_at: Option<i64>,
+    pub executed: bool,
+    pub owner_set_seqno: u32,
+    pub bump: u8,
+}
+
+impl Proposal {
+    pub fn space(num_accounts: usize, data_len: usize, num_owners: usize) -> usize {
+        8 + 32
+            + 8
+            + 32
+            + 32
+            + (4 + num_accounts * ProposalAccount::INIT_SPACE)
+            + (4 + data_len)
+            + (4 + num_owners)
+            + (1 + 8)
+            + 1
+            + 4
+            + 1
+    }
+    
+    pub fn approval_count(&self) -> usize {
+        self.approvals.iter().filter(|approved| **approved).count()
+    }
+    
+    pub fn approve(&mut self, owner_index: usize, threshold: u8, now: i64) {
+        self.approvals[owner_index] = true;
+        if self.approved_at.is_none() && self.approval_count() >= threshold as usize {
+            self.approved_at = Some(now);
+        }
+    }
+    
+    /// Earliest time the proposal can be executed under `timelock`.
+    pub fn executable_at(&self, timelock: i64) -> Result<i64> {
+        let approved_at = self.approved_at.ok_or(ErrorCode::ProposalNotApproved)?;
+        approved_at
+            .checked_add(timelock)
+            .ok_or_else(|| ErrorCode::InvalidTimelock.into())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, InitSpace)]
+pub struct ProposalAccount {
+    pub pubkey: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+impl From<&ProposalAccount> for AccountMeta {
+    fn from(account: &ProposalAccount) -> AccountMeta {
+        if account.is_writable {
+            AccountMeta::new(account.pubkey, account.is_signer)
+        } else {
+            AccountMeta::new_readonly(account.pubkey, account.is_signer)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn owners(count: usize) -> Vec<Pubkey> {
+        (0..count).map(|_| Pubkey::new_unique()).collect()
+    }
+    
+    fn proposal(num_owners: usize) -> Proposal {
+        Proposal {
+            multisig: Pubkey::new_unique(),
+            index: 0,
+            proposer: Pubkey::new_unique(),
+            program_id: Pubkey::new_unique(),
+            accounts: vec![],
+            data: vec![],
+            approvals: vec![false; num_owners],
+            approved_at: None,
+            executed: false,
+            owner_set_seqno: 0,
+            bump: 0,
+        }
+    }
+    
+    #[test]
+    fn validate_owners_bounds_the_threshold() {
+        let owners = owners(3);
+        assert!(Multisig::validate_owners(&owners, 1).is_ok());
+        assert!(Multisig::validate_owners(&owners, 3).is_ok());
+        assert_eq!(
+            Multisig::validate_owners(&owners, 0).unwrap_err(),
+            ErrorCode::InvalidThreshold.into()
+        );
+        assert_eq!(
+            Multisig::validate_owners(&owners, 4).unwrap_err(),
+            ErrorCode::InvalidThreshold.into()
+        );
+    }
+    
+    #[test]
+    fn validate_owners_rejects_bad_owner_sets() {
+        assert_eq!(
+            Multisig::validate_owners(&[], 1).unwrap_err(),
+            ErrorCode::InvalidMultisigOwners.into()
+        );
+        assert_eq!(
+            Multisig::validate_owners(&owners(MAX_MULTISIG_OWNERS + 1), 1).unwrap_err(),
+            ErrorCode::InvalidMultisigOwners.into()
+        );
+        let owner = Pubkey::new_unique();
+        assert_eq!(
+            Multisig::validate_owners(&[owner, Pubkey::new_unique(), owner], 2).unwrap_err(),
+            ErrorCode::InvalidMultisigOwners.into()
+        );
+    }
+    
+    #[test]
+    fn validate_timelock_bounds() {
+        assert!(Multisig::validate_timelock(0).is_ok());
+        assert!(Multisig::validate_timelock(MAX_MULTISIG_TIMELOCK).is_ok());
+        assert_eq!(
+            Multisig::validate_timelock(-1).unwrap_err(),
+            ErrorCode::InvalidTimelock.into()
+        );
+        assert_eq!(
+            Multisig::validate_timelock(MAX_MULTISIG_TIMELOCK + 1).unwrap_err(),
+            ErrorCode::InvalidTimelock.into()
+        );
+        assert_eq!(
+            Multisig::validate_timelock(i64::MAX).unwrap_err(),
+            ErrorCode::InvalidTimelock.into()
+        );
+    }
+    
+    #[test]
+    fn approve_records_when_the_threshold_is_reached() {
+        let mut proposal = proposal(3);
+        proposal.approve(0, 2, 100);
+        assert_eq!(proposal.approved_at, None);
+        
+        // Approving twice doesn't count twice.
+        proposal.approve(0, 2, 150);
+        assert_eq!(proposal.approval_count(), 1);
+        assert_eq!(proposal.approved_at, None);
+        
+        proposal.approve(2, 2, 200);
+        assert_eq!(proposal.approved_at, Some(200));
+        
+        // Later approvals don't restart the timelock.
+        proposal.approve(1, 2, 300);
+        assert_eq!(proposal.approval_count(), 3);
+        assert_eq!(proposal.approved_at, Some(200));
+    }
+    
+    #[test]
+    fn executable_at_runs_the_timelock_from_approval() {
+        let mut proposal = proposal(1);
+        assert_eq!(
+            proposal.executable_at(60).unwrap_err(),
+            ErrorCode::ProposalNotApproved.into()
+        );
+        
+        proposal.approve(0, 1, 1_000);
+        assert_eq!(proposal.executable_at(0).unwrap(), 1_000);
+        assert_eq!(proposal.executable_at(60).unwrap(), 1_060);
+    }
+    
+    #[test]
+    fn executable_at_rejects_overflowing_timelocks() {
+        let mut proposal = proposal(1);
+        proposal.approve(0, 1, 1_000);
+        assert_eq!(
+            proposal.executable_at(i64::MAX).unwrap_err(),
+            ErrorCode::InvalidTimelock.into()
+        );
+    }
+}
@@ -0,0 +1,173 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::config::{ProgramConfig, PAUSE_NFT};
+use crate::drops::{write_drop_table, DropEntry, DropTable, PricePayment};
+use crate::events::MysteryBoxOpened;
+use crate::nft::{commit_mint_request, MintRequest};
+use crate::supply::WeaponSupply;
+use crate::{seeds, ErrorCode, TokenData};
+
+// The mystery box is a second drop table with its own price and weights,
+// typically with the Wonder Weapons (`WeaponType::is_wonder_weapon`) given
+// small weights. Opening one commits a regular mint request against it, so
+// the weapon is revealed and minted by `mint_weapon_nft`.
+
+#[derive(Accounts)]
+pub struct InitializeMysteryBox<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        seeds = [seeds::CONFIG],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + DropTable::INIT_SPACE,
+        seeds = [seeds::MYSTERY_BOX],
+        bump,
+    )]
+    pub mystery_box: Account<'info, DropTable>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_mystery_box(
+    ctx: Context<InitializeMysteryBox>,
+    price: u64,
+    payment: PricePayment,
+    entries: Vec<DropEntry>,
+) -> Result<()> {
+    let mystery_box = &mut ctx.accounts.mystery_box;
+    write_drop_table(mystery_box, price, payment, entries)?;
+    mystery_box.bump = ctx.bumps.mystery_box;
+    
+    msg!("Mystery box initialized with {} entries", mystery_box.entries.len());
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateMysteryBox<'info> {
+    pub admin: Signer<'info>,
+    
+    #[account(
+        seeds = [seeds::CONFIG],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::MYSTERY_BOX],
+        bump = mystery_box.bump,
+    )]
+    pub mystery_box: Account<'info, DropTable>,
+}
+
+pub fn set_mystery_box(
+    ctx: Context<UpdateMysteryBox>,
+    price: u64,
+    payment: PricePayment,
+    entries: Vec<DropEntry>,
+) -> Result<()> {
+    let mystery_box = &mut ctx.accounts.mystery_box;
+    write_drop_table(mystery_box, price, payment, entries)?;
+    
+    msg!("Mystery box set: {} entries, {} $ZAP", mystery_box.entries.len(), price);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct OpenMysteryBox<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+    
+    #[account(
+        seeds = [seeds::CONFIG],
+        bump = config.bump,
+        constraint = !config.is_paused(PAUSE_NFT) @ ErrorCode::ProgramPaused,
+    )]
+    pub config: Box<Account<'info, ProgramConfig>>,
+    
+    #[account(
+        seeds = [seeds::MYSTERY_BOX],
+        bump = mystery_box.bump,
+    )]
+    pub mystery_box: Box<Account<'info, DropTable>>,
+    
+    #[account(
+        seeds = [seeds::WEAPON_SUPPLY],
+        bump = weapon_supply.bump,
+    )]
+    pub weapon_supply: Box<Account<'info, WeaponSupply>>,
+    
+    #[account(
+        init,
+        payer = player,
+        space = 8 + MintRequest::INIT_SPACE,
+        seeds = [seeds::MINT_REQUEST, player.key().as_ref()],
+        bump,
+    )]
+    pub mint_request: Box<Account<'info, MintRequest>>,
+    
+    #[account(
+        init,
+        payer = player,
+        token::mint = zap_mint,
+        token::authority = mint_request,
+        seeds = [seeds::MINT_ESCROW, mint_request.key().as_ref()],
+        bump,
+    )]
+    pub escrow: Box<Account<'info, TokenAccount>>,
+    
+    pub zap_mint: Box<Account<'info, Mint>>,
+    
+    /// Pins `zap_mint` to the $ZAP mint, see `RequestWeaponMint`.
+    #[account(
+        seeds = [seeds::TOKEN_DATA, zap_mint.key().as_ref()],
+        bump = token_data.bump,
+    )]
+    pub token_data: Box<Account<'info, TokenData>>,
+    
+    #[account(
+        mut,
+        constraint = player_token_account.owner == player.key(),
+        constraint = player_token_account.mint == zap_mint.key(),
+    )]
+    pub player_token_account: Box<Account<'info, TokenAccount>>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Buys a mystery box. The weapon inside is rolled and minted when the
+/// player reveals the resulting mint request with `mint_weapon_nft`.
+pub fn open_mystery_box(ctx: Context<OpenMysteryBox>) -> Result<()> {
+    let accounts = ctx.accounts;
+    let mystery_box = &accounts.mystery_box;
+    commit_mint_request(
+        &mut accounts.mint_request,
+        ctx.bumps.mint_request,
+        mystery_box,
+        &accounts.weapon_supply,
+        &accounts.player,
+        &accounts.player_token_account,
+        &accounts.escrow,
+        &accounts.token_program,
+    )?;
+    
+    emit!(MysteryBoxOpened {
+        player: accounts.player.key(),
+        request: accounts.mint_request.key(),
+        price: mystery_box.price,
+        payment: mystery_box.payment,
+    });
+    msg!("Mystery box opened by {}", accounts.player.key());
+    Ok(())
+}
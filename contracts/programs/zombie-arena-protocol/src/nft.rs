@@ -1,19 +1,329 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar;
 use anchor_spl::{
     associated_token::AssociatedToken,
     metadata::{
         create_metadata_accounts_v3, mpl_token_metadata::types::DataV2, CreateMetadataAccountsV3,
         Metadata as Metaplex,
     },
-    token::{mint_to, Mint, MintTo, Token, TokenAccount},
+    token::{
+        close_account, mint_to, transfer, CloseAccount, Mint, MintTo, Token, TokenAccount,
+        Transfer,
+    },
+};
+
+use crate::config::{ProgramConfig, PAUSE_NFT};
+use crate::drops::{slot_hash_at_or_after, weapon_roll, DropEntry, DropTable, PricePayment};
+use crate::events::{
+    WeaponMintRefunded, WeaponMintRequested, WeaponMinted, WeaponOwnerSynced,
+    WeaponPackAPunched, WeaponRolled,
 };
+use crate::supply::WeaponSupply;
+use crate::{burn_zap, seeds, ErrorCode, TokenData};
+
+/// Slots after its target slot that a mint request can still be revealed in,
+/// matching how far back the SlotHashes sysvar reaches.
+pub const MAX_REVEAL_SLOTS: u64 = 512;
+
+#[derive(Accounts)]
+pub struct RequestWeaponMint<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+    
+    #[account(
+        seeds = [seeds::CONFIG],
+        bump = config.bump,
+        constraint = !config.is_paused(PAUSE_NFT) @ ErrorCode::ProgramPaused,
+    )]
+    pub config: Box<Account<'info, ProgramConfig>>,
+    
+    #[account(
+        seeds = [seeds::DROP_TABLE],
+        bump = drop_table.bump,
+    )]
+    pub drop_table: Box<Account<'info, DropTable>>,
+    
+    #[account(
+        seeds = [seeds::WEAPON_SUPPLY],
+        bump = weapon_supply.bump,
+    )]
+    pub weapon_supply: Box<Account<'info, WeaponSupply>>,
+    
+    #[account(
+        init,
+        payer = player,
+        space = 8 + MintRequest::INIT_SPACE,
+        seeds = [seeds::MINT_REQUEST, player.key().as_ref()],
+        bump,
+    )]
+    pub mint_request: Box<Account<'info, MintRequest>>,
+    
+    #[account(
+        init,
+        payer = player,
+        token::mint = zap_mint,
+        token::authority = mint_request,
+        seeds = [seeds::MINT_ESCROW, mint_request.key().as_ref()],
+        bump,
+    )]
+    pub escrow: Box<Account<'info, TokenAccount>>,
+    
+    pub zap_mint: Box<Account<'info, Mint>>,
+    
+    /// Pins `zap_mint` to the $ZAP mint. An escrow in any other mint could
+    /// never be revealed or closed, leaving the player's request stuck.
+    #[account(
+        seeds = [seeds::TOKEN_DATA, zap_mint.key().as_ref()],
+        bump = token_data.bump,
+    )]
+    pub token_data: Box<Account<'info, TokenData>>,
+    
+    #[account(
+        mut,
+        constraint = player_token_account.owner == player.key(),
+        constraint = player_token_account.mint == zap_mint.key(),
+    )]
+    pub player_token_account: Box<Account<'info, TokenAccount>>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Commits to a weapon mint, moving the drop table price into escrow. The
+/// weapon is rolled by `mint_weapon_nft` from the hash of a slot that doesn't
+/// exist yet, so neither the player nor anyone else knows the outcome here.
+pub fn request_weapon_mint(ctx: Context<RequestWeaponMint>) -> Result<()> {
+    let accounts = ctx.accounts;
+    commit_mint_request(
+        &mut accounts.mint_request,
+        ctx.bumps.mint_request,
+        &accounts.drop_table,
+        &accounts.weapon_supply,
+        &accounts.player,
+        &accounts.player_token_account,
+        &accounts.escrow,
+        &accounts.token_program,
+    )
+}
+
+/// Fills in a freshly created mint request and escrows its price. The
+/// request can be revealed from the next slot on.
+#[allow(clippy::too_many_arguments)]
+pub fn commit_mint_request<'info>(
+    mint_request: &mut Account<'info, MintRequest>,
+    bump: u8,
+    drop_table: &Account<'info, DropTable>,
+    weapon_supply: &WeaponSupply,
+    player: &Signer<'info>,
+    player_token_account: &Account<'info, TokenAccount>,
+    escrow: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+) -> Result<()> {
+    require!(drop_table.has_supply(weapon_supply), ErrorCode::SupplyCapReached);
+    
+    let price = drop_table.price;
+    if price > 0 {
+        let cpi_accounts = Transfer {
+            from: player_token_account.to_account_info(),
+            to: escrow.to_account_info(),
+            authority: player.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(token_program.to_account_info(), cpi_accounts);
+        transfer(cpi_ctx, price)?;
+    }
+    
+    let slot = Clock::get()?.slot;
+    mint_request.player = player.key();
+    mint_request.drop_table = drop_table.key();
+    mint_request.price = price;
+    mint_request.payment = drop_table.payment;
+    mint_request.requested_slot = slot;
+    mint_request.target_slot = slot + 1;
+    mint_request.bump = bump;
+    
+    emit!(WeaponMintRequested {
+        player: mint_request.player,
+        request: mint_request.key(),
+        drop_table: mint_request.drop_table,
+        price,
+        target_slot: mint_request.target_slot,
+    });
+    msg!("Weapon mint requested, reveal from slot {}", mint_request.target_slot);
+    Ok(())
+}
+
+/// Rolls a mint request against its drop table, logging the roll. Fails
+/// once the request can no longer be revealed.
+fn reveal_mint_request(
+    mint_request: &Account<MintRequest>,
+    drop_table: &Account<DropTable>,
+    slot_hashes: &AccountInfo,
+) -> Result<DropEntry> {
+    let current_slot = Clock::get()?.slot;
+    require!(
+        current_slot <= mint_request.target_slot + MAX_REVEAL_SLOTS,
+        ErrorCode::RevealExpired
+    );
+    
+    let (slot, slot_hash) = {
+        let data = slot_hashes.try_borrow_data()?;
+        slot_hash_at_or_after(&data, mint_request.target_slot)?
+    };
+    let roll = weapon_roll(&slot_hash, &mint_request.key(), mint_request.requested_slot);
+    let entry = drop_table.pick(roll).ok_or(ErrorCode::InvalidDropTable)?.clone();
+    
+    emit!(WeaponRolled {
+        player: mint_request.player,
+        request: mint_request.key(),
+        drop_table: drop_table.key(),
+        slot,
+        slot_hash,
+        roll,
+        total_weight: drop_table.total_weight(),
+        weapon_type: entry.weapon_type.clone(),
+        rarity: entry.rarity.clone(),
+    });
+    Ok(entry)
+}
+
+/// Pays out an escrowed price the way the request was bought: burned, or
+/// sent to the fee recipient. The escrow is then closed to the player.
+#[allow(clippy::too_many_arguments)]
+fn settle_escrow<'info>(
+    mint_request: &Account<'info, MintRequest>,
+    escrow: &Account<'info, TokenAccount>,
+    player: &AccountInfo<'info>,
+    zap_mint: &Account<'info, Mint>,
+    fee_recipient_account: Option<&Account<'info, TokenAccount>>,
+    token_data: &mut Account<'info, TokenData>,
+    token_program: &Program<'info, Token>,
+) -> Result<()> {
+    let seeds = &[
+        seeds::MINT_REQUEST,
+        mint_request.player.as_ref(),
+        &[mint_request.bump],
+    ];
+    let signer = &[&seeds[..]];
+    
+    let amount = escrow.amount;
+    if amount > 0 {
+        match mint_request.payment {
+            PricePayment::Burn => {
+                burn_zap(
+                    token_program,
+                    zap_mint,
+                    escrow,
+                    &mint_request.to_account_info(),
+                    signer,
+                    token_data,
+                    mint_request.player,
+                    amount,
+                )?;
+            }
+            PricePayment::FeeRecipient => {
+                let fee_recipient_account =
+                    fee_recipient_account.ok_or(ErrorCode::MissingFeeRecipientAccount)?;
+                let cpi_accounts = Transfer {
+                    from: escrow.to_account_info(),
+                    to: fee_recipient_account.to_account_info(),
+                    authority: mint_request.to_account_info(),
+                };
+                let cpi_ctx = CpiContext::new_with_signer(
+                    token_program.to_account_info(),
+                    cpi_accounts,
+                    signer,
+                );
+                transfer(cpi_ctx, amount)?;
+            }
+        }
+    }
+    
+    close_escrow(mint_request, escrow, player, token_program)
+}
+
+fn close_escrow<'info>(
+    mint_request: &Account<'info, MintRequest>,
+    escrow: &Account<'info, TokenAccount>,
+    player: &AccountInfo<'info>,
+    token_program: &Program<'info, Token>,
+) -> Result<()> {
+    let seeds = &[
+        seeds::MINT_REQUEST,
+        mint_request.player.as_ref(),
+        &[mint_request.bump],
+    ];
+    let signer = &[&seeds[..]];
+    
+    let cpi_accounts = CloseAccount {
+        account: escrow.to_account_info(),
+        destination: player.clone(),
+        authority: mint_request.to_account_info(),
+    };
+    let cpi_ctx =
+        CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer);
+    close_account(cpi_ctx)
+}
 
 #[derive(Accounts)]
-#[instruction(weapon_type: WeaponType, rarity: WeaponRarity)]
 pub struct MintWeaponNFT<'info> {
     #[account(mut)]
     pub player: Signer<'info>,
     
+    // No pause check: the request is already paid for and expires after
+    // `MAX_REVEAL_SLOTS`, so blocking the reveal would forfeit the price.
+    #[account(
+        seeds = [seeds::CONFIG],
+        bump = config.bump,
+    )]
+    pub config: Box<Account<'info, ProgramConfig>>,
+    
+    #[account(
+        mut,
+        close = player,
+        seeds = [seeds::MINT_REQUEST, player.key().as_ref()],
+        bump = mint_request.bump,
+    )]
+    pub mint_request: Box<Account<'info, MintRequest>>,
+    
+    #[account(address = mint_request.drop_table)]
+    pub drop_table: Box<Account<'info, DropTable>>,
+    
+    #[account(address = sysvar::slot_hashes::ID)]
+    /// CHECK: SlotHashes sysvar, read raw for the reveal slot's hash
+    pub slot_hashes: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::WEAPON_SUPPLY],
+        bump = weapon_supply.bump,
+    )]
+    pub weapon_supply: Box<Account<'info, WeaponSupply>>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::MINT_ESCROW, mint_request.key().as_ref()],
+        bump,
+    )]
+    pub escrow: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::TOKEN_DATA, zap_mint.key().as_ref()],
+        bump = token_data.bump,
+    )]
+    pub token_data: Box<Account<'info, TokenData>>,
+    
+    #[account(mut, address = escrow.mint)]
+    pub zap_mint: Box<Account<'info, Mint>>,
+    
+    /// Only needed when the request's price goes to the fee recipient.
+    #[account(
+        mut,
+        constraint = fee_recipient_account.owner == config.fee_recipient,
+        constraint = fee_recipient_account.mint == zap_mint.key(),
+    )]
+    pub fee_recipient_account: Option<Box<Account<'info, TokenAccount>>>,
+    
     #[account(
         init,
         payer = player,
@@ -21,7 +331,7 @@ pub struct MintWeaponNFT<'info> {
         mint::authority = mint_authority,
         mint::freeze_authority = mint_authority,
     )]
-    pub weapon_mint: Account<'info, Mint>,
+    pub weapon_mint: Box<Account<'info, Mint>>,
     
     #[account(
         init,
@@ -29,7 +339,7 @@ pub struct MintWeaponNFT<'info> {
         associated_token::mint = weapon_mint,
         associated_token::authority = player,
     )]
-    pub weapon_account: Account<'info, TokenAccount>,
+    pub weapon_account: Box<Account<'info, TokenAccount>>,
     
     #[account(
         mut,
@@ -44,13 +354,13 @@ pub struct MintWeaponNFT<'info> {
         init,
         payer = player,
         space = 8 + WeaponData::INIT_SPACE,
-        seeds = [b"weapon", weapon_mint.key().as_ref()],
+        seeds = [seeds::WEAPON, weapon_mint.key().as_ref()],
         bump,
     )]
-    pub weapon_data: Account<'info, WeaponData>,
+    pub weapon_data: Box<Account<'info, WeaponData>>,
     
     #[account(
-        seeds = [b"mint_authority"],
+        seeds = [seeds::MINT_AUTHORITY],
         bump,
     )]
     /// CHECK: PDA used as mint authority
@@ -63,16 +373,39 @@ pub struct MintWeaponNFT<'info> {
     pub rent: Sysvar<'info, Rent>,
 }
 
-pub fn mint_weapon_nft(
-    ctx: Context<MintWeaponNFT>,
-    weapon_type: WeaponType,
-    rarity: WeaponRarity,
-) -> Result<()> {
+/// Reveals a mint request: rolls the weapon from the request's slot hash and
+/// the drop table, settles the escrowed price, then mints the weapon.
+///
+/// Fails with `SupplyCapReached` if the roll lands on a sold-out pair; the
+/// player then reclaims the price with `refund_sold_out_mint_request`.
+pub fn mint_weapon_nft(ctx: Context<MintWeaponNFT>) -> Result<()> {
+    let DropEntry {
+        weapon_type,
+        rarity,
+        ..
+    } = reveal_mint_request(
+        &ctx.accounts.mint_request,
+        &ctx.accounts.drop_table,
+        &ctx.accounts.slot_hashes,
+    )?;
+    ctx.accounts.weapon_supply.record_mint(&weapon_type, &rarity)?;
+    
+    settle_escrow(
+        &ctx.accounts.mint_request,
+        &ctx.accounts.escrow,
+        &ctx.accounts.player.to_account_info(),
+        &ctx.accounts.zap_mint,
+        ctx.accounts.fee_recipient_account.as_deref(),
+        &mut ctx.accounts.token_data,
+        &ctx.accounts.token_program,
+    )?;
+    
     let weapon_data = &mut ctx.accounts.weapon_data;
     weapon_data.weapon_type = weapon_type.clone();
     weapon_data.rarity = rarity.clone();
     weapon_data.level = 1;
     weapon_data.kills = 0;
+    weapon_data.xp = 0;
     weapon_data.is_pack_a_punched = false;
     weapon_data.owner = ctx.accounts.player.key();
     weapon_data.mint = ctx.accounts.weapon_mint.key();
@@ -83,10 +416,7 @@ pub fn mint_weapon_nft(
     weapon_data.damage = (base_damage as f32 * rarity_multiplier) as u32;
     
     // Mint NFT
-    let seeds = &[
-        b"mint_authority",
-        &[ctx.bumps.mint_authority],
-    ];
+    let seeds = &[seeds::MINT_AUTHORITY, &[ctx.bumps.mint_authority]];
     let signer = &[&seeds[..]];
     
     let cpi_accounts = MintTo {
@@ -138,25 +468,216 @@ pub fn mint_weapon_nft(
     
     create_metadata_accounts_v3(cpi_ctx, data_v2, true, true, None)?;
     
+    emit!(WeaponMinted {
+        player: ctx.accounts.player.key(),
+        mint: ctx.accounts.weapon_mint.key(),
+        damage: ctx.accounts.weapon_data.damage,
+        weapon_type: weapon_type.clone(),
+        rarity: rarity.clone(),
+    });
     msg!("Minted {} {} weapon NFT", rarity.to_string(), weapon_type.to_string());
     Ok(())
 }
 
+#[derive(Accounts)]
+pub struct CloseMintRequest<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+    
+    #[account(
+        seeds = [seeds::CONFIG],
+        bump = config.bump,
+    )]
+    pub config: Box<Account<'info, ProgramConfig>>,
+    
+    #[account(
+        mut,
+        close = player,
+        seeds = [seeds::MINT_REQUEST, player.key().as_ref()],
+        bump = mint_request.bump,
+    )]
+    pub mint_request: Box<Account<'info, MintRequest>>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::MINT_ESCROW, mint_request.key().as_ref()],
+        bump,
+    )]
+    pub escrow: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::TOKEN_DATA, zap_mint.key().as_ref()],
+        bump = token_data.bump,
+    )]
+    pub token_data: Box<Account<'info, TokenData>>,
+    
+    #[account(mut, address = escrow.mint)]
+    pub zap_mint: Box<Account<'info, Mint>>,
+    
+    /// Only needed when the request's price goes to the fee recipient.
+    #[account(
+        mut,
+        constraint = fee_recipient_account.owner == config.fee_recipient,
+        constraint = fee_recipient_account.mint == zap_mint.key(),
+    )]
+    pub fee_recipient_account: Option<Box<Account<'info, TokenAccount>>>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+/// Clears a request that was never revealed in time so the player can make a
+/// new one. The price is settled as if the weapon had been minted, not
+/// refunded, otherwise players could sit on rolls they don't like.
+pub fn close_expired_mint_request(ctx: Context<CloseMintRequest>) -> Result<()> {
+    let current_slot = Clock::get()?.slot;
+    require!(
+        current_slot > ctx.accounts.mint_request.target_slot + MAX_REVEAL_SLOTS,
+        ErrorCode::MintRequestNotExpired
+    );
+    
+    settle_escrow(
+        &ctx.accounts.mint_request,
+        &ctx.accounts.escrow,
+        &ctx.accounts.player.to_account_info(),
+        &ctx.accounts.zap_mint,
+        ctx.accounts.fee_recipient_account.as_deref(),
+        &mut ctx.accounts.token_data,
+        &ctx.accounts.token_program,
+    )?;
+    
+    msg!("Expired mint request closed for {}", ctx.accounts.mint_request.player);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RefundSoldOutMintRequest<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+    
+    #[account(
+        mut,
+        close = player,
+        seeds = [seeds::MINT_REQUEST, player.key().as_ref()],
+        bump = mint_request.bump,
+    )]
+    pub mint_request: Box<Account<'info, MintRequest>>,
+    
+    #[account(address = mint_request.drop_table)]
+    pub drop_table: Box<Account<'info, DropTable>>,
+    
+    #[account(address = sysvar::slot_hashes::ID)]
+    /// CHECK: SlotHashes sysvar, read raw for the reveal slot's hash
+    pub slot_hashes: UncheckedAccount<'info>,
+    
+    #[account(
+        seeds = [seeds::WEAPON_SUPPLY],
+        bump = weapon_supply.bump,
+    )]
+    pub weapon_supply: Box<Account<'info, WeaponSupply>>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::MINT_ESCROW, mint_request.key().as_ref()],
+        bump,
+    )]
+    pub escrow: Box<Account<'info, TokenAccount>>,
+    
+    #[account(
+        mut,
+        constraint = player_token_account.owner == player.key(),
+        constraint = player_token_account.mint == escrow.mint,
+    )]
+    pub player_token_account: Box<Account<'info, TokenAccount>>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+/// Returns the escrowed price of a request whose roll landed on a sold-out
+/// (type, rarity) pair, which `mint_weapon_nft` can't mint.
+pub fn refund_sold_out_mint_request(ctx: Context<RefundSoldOutMintRequest>) -> Result<()> {
+    let DropEntry {
+        weapon_type,
+        rarity,
+        ..
+    } = reveal_mint_request(
+        &ctx.accounts.mint_request,
+        &ctx.accounts.drop_table,
+        &ctx.accounts.slot_hashes,
+    )?;
+    require!(
+        ctx.accounts.weapon_supply.remaining(&weapon_type, &rarity) == 0,
+        ErrorCode::RollNotSoldOut
+    );
+    
+    let mint_request = &ctx.accounts.mint_request;
+    let escrow = &ctx.accounts.escrow;
+    let amount = escrow.amount;
+    if amount > 0 {
+        let seeds = &[
+            seeds::MINT_REQUEST,
+            mint_request.player.as_ref(),
+            &[mint_request.bump],
+        ];
+        let signer = &[&seeds[..]];
+        let cpi_accounts = Transfer {
+            from: escrow.to_account_info(),
+            to: ctx.accounts.player_token_account.to_account_info(),
+            authority: mint_request.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer,
+        );
+        transfer(cpi_ctx, amount)?;
+    }
+    close_escrow(
+        mint_request,
+        escrow,
+        &ctx.accounts.player.to_account_info(),
+        &ctx.accounts.token_program,
+    )?;
+    
+    emit!(WeaponMintRefunded {
+        player: mint_request.player,
+        request: mint_request.key(),
+        weapon_type,
+        rarity,
+        amount,
+    });
+    msg!("Refunded {} $ZAP for a sold-out roll", amount);
+    Ok(())
+}
+
 #[derive(Accounts)]
 pub struct PackAPunchWeapon<'info> {
     #[account(mut)]
     pub player: Signer<'info>,
     
+    #[account(
+        seeds = [seeds::CONFIG],
+        bump = config.bump,
+        constraint = !config.is_paused(PAUSE_NFT) @ ErrorCode::ProgramPaused,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
     #[account(
         mut,
-        seeds = [b"weapon", weapon_mint.key().as_ref()],
+        seeds = [seeds::WEAPON, weapon_mint.key().as_ref()],
         bump,
-        constraint = weapon_data.owner == player.key(),
     )]
     pub weapon_data: Account<'info, WeaponData>,
     
     pub weapon_mint: Account<'info, Mint>,
     
+    /// The player's token account holding the weapon, which is what proves
+    /// ownership once the NFT has changed hands.
+    #[account(
+        constraint = weapon_data.is_held_in(&weapon_account, &player.key()) @ ErrorCode::NotWeaponHolder,
+    )]
+    pub weapon_account: Account<'info, TokenAccount>,
+    
     #[account(
         mut,
         constraint = player_token_account.owner == player.key(),
@@ -166,36 +687,105 @@ pub struct PackAPunchWeapon<'info> {
     #[account(mut)]
     pub zap_mint: Account<'info, Mint>,
     
+    #[account(
+        mut,
+        seeds = [seeds::TOKEN_DATA, zap_mint.key().as_ref()],
+        bump = token_data.bump,
+    )]
+    pub token_data: Account<'info, TokenData>,
+    
     pub token_program: Program<'info, Token>,
 }
 
 pub fn pack_a_punch_weapon(ctx: Context<PackAPunchWeapon>) -> Result<()> {
-    let weapon_data = &mut ctx.accounts.weapon_data;
-    
     require!(
-        !weapon_data.is_pack_a_punched,
+        !ctx.accounts.weapon_data.is_pack_a_punched,
         ErrorCode::AlreadyPackAPunched
     );
     
     // Burn tokens for upgrade
     let pack_a_punch_cost = 5000u64;
-    let cpi_accounts = anchor_spl::token::Burn {
-        mint: ctx.accounts.zap_mint.to_account_info(),
-        from: ctx.accounts.player_token_account.to_account_info(),
-        authority: ctx.accounts.player.to_account_info(),
-    };
-    let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
-    anchor_spl::token::burn(cpi_ctx, pack_a_punch_cost)?;
+    burn_zap(
+        &ctx.accounts.token_program,
+        &ctx.accounts.zap_mint,
+        &ctx.accounts.player_token_account,
+        &ctx.accounts.player.to_account_info(),
+        &[],
+        &mut ctx.accounts.token_data,
+        ctx.accounts.player.key(),
+        pack_a_punch_cost,
+    )?;
+    
+    let weapon_data = &mut ctx.accounts.weapon_data;
     
-    // Upgrade weapon
+    // Upgrade weapon. The bonus level is capped like XP levels are in
+    // `record_weapon_kills`, the doubled damage is Pack-a-Punch's own.
+    weapon_data.owner = ctx.accounts.player.key();
     weapon_data.is_pack_a_punched = true;
-    weapon_data.damage *= 2;
-    weapon_data.level += 1;
+    weapon_data.damage = weapon_data.damage.saturating_mul(2);
+    weapon_data.level = (weapon_data.level + 1).min(weapon_data.rarity.max_level());
     
+    emit!(WeaponPackAPunched {
+        player: ctx.accounts.player.key(),
+        mint: weapon_data.mint,
+        cost: pack_a_punch_cost,
+        damage: weapon_data.damage,
+        level: weapon_data.level,
+    });
     msg!("Pack-a-Punched weapon: {}", weapon_data.mint);
     Ok(())
 }
 
+#[derive(Accounts)]
+pub struct SyncWeaponOwner<'info> {
+    #[account(
+        mut,
+        seeds = [seeds::WEAPON, weapon_data.mint.as_ref()],
+        bump,
+    )]
+    pub weapon_data: Account<'info, WeaponData>,
+    
+    #[account(
+        constraint = weapon_account.mint == weapon_data.mint @ ErrorCode::NotWeaponHolder,
+        constraint = weapon_account.amount == 1 @ ErrorCode::NotWeaponHolder,
+    )]
+    pub weapon_account: Account<'info, TokenAccount>,
+}
+
+/// Points `WeaponData.owner` at whoever currently holds the NFT, e.g. after
+/// a marketplace sale. Anyone can call it since it only mirrors token state.
+pub fn sync_weapon_owner(ctx: Context<SyncWeaponOwner>) -> Result<()> {
+    let weapon_data = &mut ctx.accounts.weapon_data;
+    let previous_owner = weapon_data.owner;
+    weapon_data.owner = ctx.accounts.weapon_account.owner;
+    
+    emit!(WeaponOwnerSynced {
+        mint: weapon_data.mint,
+        previous_owner,
+        owner: weapon_data.owner,
+    });
+    msg!("Weapon {} owner synced to {}", weapon_data.mint, weapon_data.owner);
+    Ok(())
+}
+
+/// A paid weapon mint waiting to be revealed by `mint_weapon_nft`.
+#[account]
+#[derive(InitSpace)]
+pub struct MintRequest {
+    pub player: Pubkey,
+    /// Drop table the weapon is rolled from.
+    pub drop_table: Pubkey,
+    /// $ZAP held in the request's escrow until it's revealed.
+    pub price: u64,
+    /// How the price is settled, fixed when the request is made.
+    pub payment: PricePayment,
+    /// Slot the request was made in, mixed into the roll.
+    pub requested_slot: u64,
+    /// First slot whose hash may decide the roll.
+    pub target_slot: u64,
+    pub bump: u8,
+}
+
 #[account]
 #[derive(InitSpace)]
 pub struct WeaponData {
@@ -204,12 +794,26 @@ pub struct WeaponData {
     pub level: u8,
     pub damage: u32,
     pub kills: u64,
+    /// Experience from `record_weapon_kills`, see `WeaponLevelConfig`.
+    pub xp: u64,
     pub is_pack_a_punched: bool,
+    /// Last known holder. Only a cache for indexers: it goes stale when the
+    /// NFT is transferred until `sync_weapon_owner` or an upgrade refreshes
+    /// it, so instructions check the holder's token account instead.
     pub owner: Pubkey,
     pub mint: Pubkey,
     pub created_at: i64,
 }
 
+impl WeaponData {
+    /// Whether `weapon_account` is `holder`'s token account holding this weapon.
+    pub fn is_held_in(&self, weapon_account: &TokenAccount, holder: &Pubkey) -> bool {
+        weapon_account.mint == self.mint
+            && weapon_account.owner == *holder
+            && weapon_account.amount == 1
+    }
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, InitSpace)]
 pub enum WeaponType {
     // Assault Rifles
@@ -234,6 +838,27 @@ pub enum WeaponType {
 }
 
 impl WeaponType {
+    pub const COUNT: usize = 14;
+    
+    pub fn index(&self) -> usize {
+        match self {
+            WeaponType::AK47 => 0,
+            WeaponType::M16 => 1,
+            WeaponType::Galil => 2,
+            WeaponType::FAMAS => 3,
+            WeaponType::MP40 => 4,
+            WeaponType::AK74u => 5,
+            WeaponType::MP5 => 6,
+            WeaponType::Olympia => 7,
+            WeaponType::SPAS12 => 8,
+            WeaponType::L96A1 => 9,
+            WeaponType::Dragunov => 10,
+            WeaponType::Raygun => 11,
+            WeaponType::Thundergun => 12,
+            WeaponType::WunderWaffe => 13,
+        }
+    }
+    
     pub fn base_damage(&self) -> u32 {
         match self {
             WeaponType::AK47 => 150,
@@ -271,6 +896,13 @@ impl WeaponType {
             WeaponType::WunderWaffe => "Wunderwaffe DG-2",
         }
     }
+    
+    pub fn is_wonder_weapon(&self) -> bool {
+        matches!(
+            self,
+            WeaponType::Raygun | WeaponType::Thundergun | WeaponType::WunderWaffe
+        )
+    }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, InitSpace)]
@@ -284,6 +916,31 @@ pub enum WeaponRarity {
 }
 
 impl WeaponRarity {
+    pub const COUNT: usize = 6;
+    
+    pub fn index(&self) -> usize {
+        match self {
+            WeaponRarity::Common => 0,
+            WeaponRarity::Uncommon => 1,
+            WeaponRarity::Rare => 2,
+            WeaponRarity::Epic => 3,
+            WeaponRarity::Legendary => 4,
+            WeaponRarity::Mythic => 5,
+        }
+    }
+    
+    /// Level cap for weapons of this rarity, at most `MAX_WEAPON_LEVEL`.
+    pub fn max_level(&self) -> u8 {
+        match self {
+            WeaponRarity::Common => 5,
+            WeaponRarity::Uncommon => 10,
+            WeaponRarity::Rare => 15,
+            WeaponRarity::Epic => 20,
+            WeaponRarity::Legendary => 25,
+            WeaponRarity::Mythic => 30,
+        }
+    }
+    
     pub fn damage_multiplier(&self) -> f32 {
         match self {
             WeaponRarity::Common => 1.0,
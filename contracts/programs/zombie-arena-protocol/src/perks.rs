@@ -0,0 +1,185 @@
+use anchor_lang::prelude::*;
+
+use crate::config::{ProgramConfig, PAUSE_TOKEN};
+use crate::events::*;
+use crate::{seeds, ActivePerk, ErrorCode, PerkType, PlayerState, BASE_PERK_SLOTS, MAX_PERK_SLOTS};
+
+#[derive(Accounts)]
+pub struct InitializePerkTable<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        seeds = [seeds::CONFIG],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + PerkTable::INIT_SPACE,
+        seeds = [seeds::PERK_TABLE],
+        bump,
+    )]
+    pub perk_table: Account<'info, PerkTable>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_perk_table(ctx: Context<InitializePerkTable>) -> Result<()> {
+    let perk_table = &mut ctx.accounts.perk_table;
+    perk_table.prices = [0; PerkType::COUNT];
+    perk_table.effects = [PerkEffect::None; PerkType::COUNT];
+    perk_table.effects[PerkType::MuleKick.index()] = PerkEffect::ExtraPerkSlots(1);
+    perk_table.effects[PerkType::QuickRevive.index()] = PerkEffect::ReviveCredits(1);
+    perk_table.bump = ctx.bumps.perk_table;
+    
+    msg!("Perk price table initialized");
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdatePerkTable<'info> {
+    pub admin: Signer<'info>,
+    
+    #[account(
+        seeds = [seeds::CONFIG],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::PERK_TABLE],
+        bump = perk_table.bump,
+    )]
+    pub perk_table: Account<'info, PerkTable>,
+}
+
+pub fn set_perk_price(ctx: Context<UpdatePerkTable>, perk: PerkType, price: u64) -> Result<()> {
+    let perk_table = &mut ctx.accounts.perk_table;
+    perk_table.prices[perk.index()] = price;
+    
+    emit!(PerkTableUpdated {
+        effect: perk_table.effect(&perk),
+        perk: perk.clone(),
+        price,
+    });
+    msg!("Price for {:?} set to {} $ZAP", perk, price);
+    Ok(())
+}
+
+pub fn set_perk_effect(
+    ctx: Context<UpdatePerkTable>,
+    perk: PerkType,
+    effect: PerkEffect,
+) -> Result<()> {
+    let perk_table = &mut ctx.accounts.perk_table;
+    perk_table.effects[perk.index()] = effect;
+    
+    emit!(PerkTableUpdated {
+        price: perk_table.prices[perk.index()],
+        perk: perk.clone(),
+        effect,
+    });
+    msg!("Effect for {:?} set to {:?}", perk, effect);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ConsumeRevive<'info> {
+    #[account(
+        constraint = config.is_game_server(&game_server.key()) @ ErrorCode::Unauthorized,
+    )]
+    pub game_server: Signer<'info>,
+    
+    #[account(
+        seeds = [seeds::CONFIG],
+        bump = config.bump,
+        constraint = !config.is_paused(PAUSE_TOKEN) @ ErrorCode::ProgramPaused,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        seeds = [seeds::PERK_TABLE],
+        bump = perk_table.bump,
+    )]
+    pub perk_table: Account<'info, PerkTable>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::PLAYER_STATE, player_state.player.as_ref()],
+        bump = player_state.bump,
+    )]
+    pub player_state: Account<'info, PlayerState>,
+}
+
+pub fn consume_revive(ctx: Context<ConsumeRevive>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let player_state = &mut ctx.accounts.player_state;
+    // Credits go with the perk that granted them, so drop expired ones first.
+    player_state.prune_expired_perks(now, &ctx.accounts.perk_table);
+    require!(player_state.revive_credits > 0, ErrorCode::NoReviveCredits);
+    player_state.revive_credits -= 1;
+    
+    emit!(ReviveConsumed {
+        player: player_state.player,
+        revive_credits: player_state.revive_credits,
+    });
+    msg!(
+        "Revive used by {}, {} left",
+        player_state.player,
+        player_state.revive_credits
+    );
+    Ok(())
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct PerkTable {
+    /// $ZAP burned to activate each perk, indexed by `PerkType::index`. Zero
+    /// means the perk is not for sale.
+    pub prices: [u64; PerkType::COUNT],
+    /// What each perk does on-chain, indexed by `PerkType::index`.
+    pub effects: [PerkEffect; PerkType::COUNT],
+    pub bump: u8,
+}
+
+impl PerkTable {
+    pub fn price(&self, perk: &PerkType) -> Result<u64> {
+        let price = self.prices[perk.index()];
+        require!(price > 0, ErrorCode::PerkNotForSale);
+        Ok(price)
+    }
+    
+    pub fn effect(&self, perk: &PerkType) -> PerkEffect {
+        self.effects[perk.index()]
+    }
+    
+    /// Perk slots available with `active_perks` in effect.
+    pub fn perk_slots(&self, active_perks: &[ActivePerk]) -> usize {
+        let extra_slots: usize = active_perks
+            .iter()
+            .map(|active| match self.effect(&active.perk) {
+                PerkEffect::ExtraPerkSlots(slots) => slots as usize,
+                _ => 0,
+            })
+            .sum();
+        (BASE_PERK_SLOTS + extra_slots).min(MAX_PERK_SLOTS)
+    }
+}
+
+/// On-chain behaviour attached to a perk. New perks pick up behaviour by
+/// being given one of these in the perk table.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, InitSpace)]
+pub enum PerkEffect {
+    /// Purely a game-side buff.
+    None,
+    /// Raises the number of perks that can be active at once while held.
+    ExtraPerkSlots(u8),
+    /// Grants revive credits on activation, spent through `consume_revive`.
+    ReviveCredits(u8),
+}
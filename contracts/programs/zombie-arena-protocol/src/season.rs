@@ -0,0 +1,398 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+use crate::config::ProgramConfig;
+use crate::events::{SeasonRewardClaimed, SeasonStarted};
+use crate::stats::SeasonStats;
+use crate::{seeds, ErrorCode, PlayerState, TokenData};
+
+pub const MAX_REWARD_BRACKETS: usize = 10;
+
+// Weights turning a player's season totals into a single score. Surviving
+// deep matters most, then keeping the team alive, then raw kills.
+pub const SCORE_PER_KILL: u64 = 1;
+pub const SCORE_PER_HEADSHOT: u64 = 1;
+pub const SCORE_PER_REVIVE: u64 = 5;
+pub const SCORE_PER_ROUND: u64 = 10;
+
+// A player's record for a season lives in `PlayerState.season_stats` while
+// the season runs and moves to `previous_season_stats` when the next one
+// starts, so rewards for a season can be claimed until the season after it
+// is over.
+
+#[derive(Accounts)]
+#[instruction(season_id: u32)]
+pub struct StartSeason<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::CONFIG],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Season::INIT_SPACE,
+        seeds = [seeds::SEASON, &season_id.to_le_bytes()],
+        bump,
+    )]
+    pub season: Account<'info, Season>,
+    
+    #[account(
+        init,
+        payer = admin,
+        token::mint = mint,
+        token::authority = season,
+        seeds = [seeds::SEASON_POOL, season.key().as_ref()],
+        bump,
+    )]
+    pub reward_pool: Account<'info, TokenAccount>,
+    
+    #[account(
+        seeds = [seeds::TOKEN_DATA, mint.key().as_ref()],
+        bump = token_data.bump,
+    )]
+    pub token_data: Account<'info, TokenData>,
+    
+    pub mint: Account<'info, Mint>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Opens a new season, making it the one `record_match` attributes stats to.
+/// The reward pool is funded by transferring $ZAP into it.
+pub fn start_season(
+    ctx: Context<StartSeason>,
+    season_id: u32,
+    end_time: i64,
+    reward_brackets: Vec<RewardBracket>,
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    require!(season_id > config.current_season, ErrorCode::InvalidSeason);
+    
+    let now = Clock::get()?.unix_timestamp;
+    require!(end_time > now, ErrorCode::InvalidSeasonTime);
+    Season::validate_brackets(&reward_brackets)?;
+    
+    config.current_season = season_id;
+    
+    let season = &mut ctx.accounts.season;
+    season.id = season_id;
+    season.start_time = now;
+    season.end_time = end_time;
+    season.reward_brackets = reward_brackets;
+    season.total_claimed = 0;
+    season.claims = 0;
+    season.bump = ctx.bumps.season;
+    season.reward_pool_bump = ctx.bumps.reward_pool;
+    
+    emit!(SeasonStarted {
+        season: season_id,
+        start_time: now,
+        end_time,
+    });
+    msg!("Season {} started, ends at {}", season_id, end_time);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClaimSeasonReward<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::SEASON, &season.id.to_le_bytes()],
+        bump = season.bump,
+    )]
+    pub season: Account<'info, Season>,
+    
+    #[account(
+        seeds = [seeds::PLAYER_STATE, player.key().as_ref()],
+        bump = player_state.bump,
+    )]
+    pub player_state: Account<'info, PlayerState>,
+    
+    #[account(
+        init,
+        payer = player,
+        space = 8 + SeasonClaim::INIT_SPACE,
+        seeds = [seeds::SEASON_CLAIM, season.key().as_ref(), player.key().as_ref()],
+        bump,
+    )]
+    pub season_claim: Account<'info, SeasonClaim>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::SEASON_POOL, season.key().as_ref()],
+        bump = season.reward_pool_bump,
+    )]
+    pub reward_pool: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = player_token_account.owner == player.key(),
+        constraint = player_token_account.mint == reward_pool.mint,
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn claim_season_reward(ctx: Context<ClaimSeasonReward>) -> Result<()> {
+    let season = &mut ctx.accounts.season;
+    
+    let now = Clock::get()?.unix_timestamp;
+    require!(now >= season.end_time, ErrorCode::SeasonNotOver);
+    
+    let score = ctx
+        .accounts
+        .player_state
+        .season_record(season.id)
+        .map(SeasonStats::score)
+        .ok_or(ErrorCode::NoSeasonReward)?;
+    let reward = season.reward_for(score);
+    require!(reward > 0, ErrorCode::NoSeasonReward);
+    require!(
+        ctx.accounts.reward_pool.amount >= reward,
+        ErrorCode::SeasonPoolDepleted
+    );
+    
+    let season_id = season.id.to_le_bytes();
+    let seeds = &[seeds::SEASON, &season_id, &[season.bump]];
+    let signer = &[&seeds[..]];
+    
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.reward_pool.to_account_info(),
+        to: ctx.accounts.player_token_account.to_account_info(),
+        authority: season.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer,
+    );
+    token::transfer(cpi_ctx, reward)?;
+    
+    season.total_claimed += reward;
+    season.claims += 1;
+    
+    let season_claim = &mut ctx.accounts.season_claim;
+    season_claim.season = season.id;
+    season_claim.player = ctx.accounts.player.key();
+    season_claim.score = score;
+    season_claim.amount = reward;
+    season_claim.bump = ctx.bumps.season_claim;
+    
+    emit!(SeasonRewardClaimed {
+        season: season.id,
+        player: season_claim.player,
+        score,
+        amount: reward,
+    });
+    msg!(
+        "Season {} reward: {} $ZAP to {} for score {}",
+        season.id,
+        reward,
+        season_claim.player,
+        score
+    );
+    Ok(())
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Season {
+    pub id: u32,
+    pub start_time: i64,
+    pub end_time: i64,
+    /// Payouts by score, highest `min_score` first.
+    #[max_len(MAX_REWARD_BRACKETS)]
+    pub reward_brackets: Vec<RewardBracket>,
+    pub total_claimed: u64,
+    pub claims: u32,
+    pub bump: u8,
+    pub reward_pool_bump: u8,
+}
+
+impl Season {
+    pub fn validate_brackets(brackets: &[RewardBracket]) -> Result<()> {
+        require!(
+            !brackets.is_empty() && brackets.len() <= MAX_REWARD_BRACKETS,
+            ErrorCode::InvalidRewardBrackets
+        );
+        require!(
+            brackets.windows(2).all(|pair| pair[0].min_score > pair[1].min_score),
+            ErrorCode::InvalidRewardBrackets
+        );
+        Ok(())
+    }
+    
+    pub fn is_live(&self, now: i64) -> bool {
+        now >= self.start_time && now < self.end_time
+    }
+    
+    /// Reward of the highest bracket `score` reaches, zero if it reaches none.
+    pub fn reward_for(&self, score: u64) -> u64 {
+        self.reward_brackets
+            .iter()
+            .find(|bracket| score >= bracket.min_score)
+            .map_or(0, |bracket| bracket.reward)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, InitSpace)]
+pub struct RewardBracket {
+    pub min_score: u64,
+    /// $ZAP paid to each player whose score reaches `min_score`.
+    pub reward: u64,
+}
+
+/// Marks a player's reward for a season as paid.
+#[account]
+#[derive(InitSpace)]
+pub struct SeasonClaim {
+    pub season: u32,
+    pub player: Pubkey,
+    pub score: u64,
+    pub amount: u64,
+    pub bump: u8,
+}
+
+impl SeasonStats {
+    pub fn score(&self) -> u64 {
+        self.kills
+            .saturating_mul(SCORE_PER_KILL)
+            .saturating_add(self.headshots.saturating_mul(SCORE_PER_HEADSHOT))
+            .saturating_add(self.revives.saturating_mul(SCORE_PER_REVIVE))
+            .saturating_add(self.rounds_survived.saturating_mul(SCORE_PER_ROUND))
+    }
+}
+
+impl PlayerState {
+    /// The player's totals for `season`, if they are still on record.
+    pub fn season_record(&self, season: u32) -> Option<&SeasonStats> {
+        [&self.season_stats, &self.previous_season_stats]
+            .into_iter()
+            .find(|stats| stats.season == season)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stats::MatchStats;
+    
+    fn season(brackets: &[(u64, u64)]) -> Season {
+        Season {
+            id: 1,
+            start_time: 0,
+            end_time: 100,
+            reward_brackets: brackets
+                .iter()
+                .map(|&(min_score, reward)| RewardBracket { min_score, reward })
+                .collect(),
+            total_claimed: 0,
+            claims: 0,
+            bump: 0,
+            reward_pool_bump: 0,
+        }
+    }
+    
+    fn match_stats(kills: u64) -> MatchStats {
+        MatchStats {
+            kills,
+            headshots: 0,
+            revives: 0,
+            rounds_survived: 1,
+            play_time: 60,
+        }
+    }
+    
+    #[test]
+    fn reward_for_picks_the_highest_bracket_reached() {
+        let season = season(&[(1_000, 500), (100, 50), (10, 5)]);
+        assert_eq!(season.reward_for(5_000), 500);
+        assert_eq!(season.reward_for(999), 50);
+        assert_eq!(season.reward_for(50), 5);
+    }
+    
+    #[test]
+    fn reward_for_bracket_edges() {
+        let season = season(&[(1_000, 500), (100, 50), (10, 5)]);
+        assert_eq!(season.reward_for(1_000), 500);
+        assert_eq!(season.reward_for(100), 50);
+        assert_eq!(season.reward_for(10), 5);
+        assert_eq!(season.reward_for(9), 0);
+        assert_eq!(season.reward_for(0), 0);
+    }
+    
+    #[test]
+    fn validate_brackets_requires_strictly_descending_min_scores() {
+        let ascending = season(&[(10, 5), (100, 50)]).reward_brackets;
+        let duplicate = season(&[(100, 50), (100, 40)]).reward_brackets;
+        let too_many = season(&[(1, 1); MAX_REWARD_BRACKETS + 1]).reward_brackets;
+        assert!(Season::validate_brackets(&season(&[(100, 50), (10, 5)]).reward_brackets).is_ok());
+        assert_eq!(
+            Season::validate_brackets(&ascending).unwrap_err(),
+            ErrorCode::InvalidRewardBrackets.into()
+        );
+        assert_eq!(
+            Season::validate_brackets(&duplicate).unwrap_err(),
+            ErrorCode::InvalidRewardBrackets.into()
+        );
+        assert_eq!(
+            Season::validate_brackets(&[]).unwrap_err(),
+            ErrorCode::InvalidRewardBrackets.into()
+        );
+        assert_eq!(
+            Season::validate_brackets(&too_many).unwrap_err(),
+            ErrorCode::InvalidRewardBrackets.into()
+        );
+    }
+    
+    #[test]
+    fn is_live_from_start_until_end() {
+        let season = season(&[(10, 5)]);
+        assert!(!season.is_live(-1));
+        assert!(season.is_live(0));
+        assert!(season.is_live(99));
+        assert!(!season.is_live(100));
+    }
+    
+    #[test]
+    fn new_season_rolls_the_record_over_to_previous_season_stats() {
+        let mut player_state = crate::tests::player_state();
+        player_state.record_match(&match_stats(10), Some(1));
+        player_state.record_match(&match_stats(5), Some(1));
+        assert_eq!(player_state.season_stats.kills, 15);
+        
+        player_state.record_match(&match_stats(3), Some(2));
+        assert_eq!(player_state.previous_season_stats.season, 1);
+        assert_eq!(player_state.previous_season_stats.kills, 15);
+        assert_eq!(player_state.season_stats.season, 2);
+        assert_eq!(player_state.season_stats.kills, 3);
+        assert_eq!(player_state.season_record(1).unwrap().score(), 15 + 2 * SCORE_PER_ROUND);
+        assert_eq!(player_state.season_record(2).unwrap().kills, 3);
+        
+        player_state.record_match(&match_stats(1), Some(3));
+        assert!(player_state.season_record(1).is_none());
+        assert_eq!(player_state.previous_season_stats.season, 2);
+    }
+    
+    #[test]
+    fn matches_outside_a_season_leave_season_records_alone() {
+        let mut player_state = crate::tests::player_state();
+        player_state.record_match(&match_stats(10), Some(1));
+        player_state.record_match(&match_stats(4), None);
+        assert_eq!(player_state.total_kills, 14);
+        assert_eq!(player_state.season_stats.kills, 10);
+        assert_eq!(player_state.previous_season_stats, SeasonStats::default());
+    }
+}
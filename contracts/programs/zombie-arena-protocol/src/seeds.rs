@@ -0,0 +1,40 @@
+// Seed prefixes of every PDA the program derives. Clients such as the SDK
+// derive addresses from these same constants rather than copies of them.
+
+pub const CONFIG: &[u8] = b"config";
+pub const TOKEN_DATA: &[u8] = b"token_data";
+/// With a mint key: authority of that $ZAP mint. Alone: mint and freeze
+/// authority of weapon NFTs and achievement badges.
+pub const MINT_AUTHORITY: &[u8] = b"mint_authority";
+pub const PLAYER_STATE: &[u8] = b"player_state";
+pub const PERK_TABLE: &[u8] = b"perk_table";
+
+pub const DROP_TABLE: &[u8] = b"drop_table";
+pub const MYSTERY_BOX: &[u8] = b"mystery_box";
+pub const WEAPON_LEVELS: &[u8] = b"weapon_levels";
+pub const WEAPON_SUPPLY: &[u8] = b"weapon_supply";
+pub const MINT_REQUEST: &[u8] = b"mint_request";
+pub const MINT_ESCROW: &[u8] = b"mint_escrow";
+pub const WEAPON: &[u8] = b"weapon";
+
+pub const TOURNAMENT: &[u8] = b"tournament";
+pub const TOURNAMENT_ENTRY: &[u8] = b"tournament_entry";
+pub const PRIZE_POOL: &[u8] = b"prize_pool";
+pub const TOURNAMENT_LEADERBOARD: &[u8] = b"tournament_leaderboard";
+pub const GUILD: &[u8] = b"guild";
+pub const GUILD_TREASURY: &[u8] = b"guild_treasury";
+pub const GLOBAL_LEADERBOARD: &[u8] = b"global_leaderboard";
+
+pub const MULTISIG: &[u8] = b"multisig";
+pub const MULTISIG_SIGNER: &[u8] = b"multisig_signer";
+pub const PROPOSAL: &[u8] = b"proposal";
+
+pub const SEASON: &[u8] = b"season";
+pub const SEASON_POOL: &[u8] = b"season_pool";
+pub const SEASON_CLAIM: &[u8] = b"season_claim";
+
+pub const ACHIEVEMENT: &[u8] = b"achievement";
+pub const BADGE: &[u8] = b"badge";
+pub const BADGE_MINT: &[u8] = b"badge_mint";
+pub const CHALLENGE: &[u8] = b"challenge";
+pub const CHALLENGE_COMPLETION: &[u8] = b"challenge_completion";
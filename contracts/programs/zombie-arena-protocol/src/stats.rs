@@ -0,0 +1,143 @@
+use anchor_lang::prelude::*;
+
+use crate::config::{ProgramConfig, PAUSE_TOKEN};
+use crate::events::MatchRecorded;
+use crate::leaderboard::GlobalLeaderboard;
+use crate::season::Season;
+use crate::{seeds, ErrorCode, PlayerState};
+
+#[derive(Accounts)]
+pub struct RecordMatch<'info> {
+    #[account(
+        constraint = config.is_game_server(&game_server.key()) @ ErrorCode::Unauthorized,
+    )]
+    pub game_server: Signer<'info>,
+    
+    #[account(
+        seeds = [seeds::CONFIG],
+        bump = config.bump,
+        constraint = !config.is_paused(PAUSE_TOKEN) @ ErrorCode::ProgramPaused,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::PLAYER_STATE, player_state.player.as_ref()],
+        bump = player_state.bump,
+    )]
+    pub player_state: Account<'info, PlayerState>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::GLOBAL_LEADERBOARD],
+        bump = global_leaderboard.bump,
+    )]
+    pub global_leaderboard: Box<Account<'info, GlobalLeaderboard>>,
+    
+    /// The current season. Without it, or once it has ended, the match only
+    /// counts towards lifetime stats.
+    #[account(
+        seeds = [seeds::SEASON, &season.id.to_le_bytes()],
+        bump = season.bump,
+        constraint = season.id == config.current_season @ ErrorCode::InvalidSeason,
+    )]
+    pub season: Option<Box<Account<'info, Season>>>,
+}
+
+pub fn record_match(ctx: Context<RecordMatch>, stats: MatchStats) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let season = ctx
+        .accounts
+        .season
+        .as_ref()
+        .filter(|season| season.is_live(now))
+        .map(|season| season.id);
+    let player_state = &mut ctx.accounts.player_state;
+    player_state.record_match(&stats, season);
+    
+    ctx.accounts.global_leaderboard.record(
+        player_state.player,
+        player_state.highest_round,
+        player_state.total_kills,
+    );
+    
+    emit!(MatchRecorded {
+        player: player_state.player,
+        season,
+        stats: stats.clone(),
+        highest_round: player_state.highest_round,
+        total_kills: player_state.total_kills,
+    });
+    msg!(
+        "Recorded match for {}: {} kills, round {}",
+        player_state.player,
+        stats.kills,
+        stats.rounds_survived
+    );
+    Ok(())
+}
+
+/// One match's results as reported by the game server.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct MatchStats {
+    pub kills: u64,
+    pub headshots: u64,
+    pub revives: u64,
+    pub rounds_survived: u32,
+    /// Seconds played.
+    pub play_time: u64,
+}
+
+/// A player's totals for a single season.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, InitSpace)]
+pub struct SeasonStats {
+    pub season: u32,
+    pub kills: u64,
+    pub headshots: u64,
+    pub revives: u64,
+    pub rounds_survived: u64,
+    pub highest_round: u32,
+    pub play_time: u64,
+    pub matches_played: u64,
+}
+
+impl SeasonStats {
+    pub fn add(&mut self, stats: &MatchStats) {
+        self.kills = self.kills.saturating_add(stats.kills);
+        self.headshots = self.headshots.saturating_add(stats.headshots);
+        self.revives = self.revives.saturating_add(stats.revives);
+        self.rounds_survived = self.rounds_survived.saturating_add(stats.rounds_survived as u64);
+        self.highest_round = self.highest_round.max(stats.rounds_survived);
+        self.play_time = self.play_time.saturating_add(stats.play_time);
+        self.matches_played = self.matches_played.saturating_add(1);
+    }
+}
+
+impl PlayerState {
+    /// Adds a match to the lifetime totals and, during a season, to that
+    /// season's record. The first match of a new season starts a fresh record
+    /// and keeps the last one in `previous_season_stats` for reward claims.
+    pub fn record_match(&mut self, stats: &MatchStats, season: Option<u32>) {
+        self.total_kills = self.total_kills.saturating_add(stats.kills);
+        self.total_headshots = self.total_headshots.saturating_add(stats.headshots);
+        self.total_revives = self.total_revives.saturating_add(stats.revives);
+        self.total_rounds_survived = self
+            .total_rounds_survived
+            .saturating_add(stats.rounds_survived as u64);
+        self.highest_round = self.highest_round.max(stats.rounds_survived);
+        self.total_play_time = self.total_play_time.saturating_add(stats.play_time);
+        self.matches_played = self.matches_played.saturating_add(1);
+        
+        let Some(season) = season else {
+            return;
+        };
+        if self.season_stats.season != season {
+            let fresh = SeasonStats {
+                season,
+                ..SeasonStats::default()
+            };
+            self.previous_season_stats = std::mem::replace(&mut self.season_stats, fresh);
+        }
+        self.season_stats.add(stats);
+    }
+}
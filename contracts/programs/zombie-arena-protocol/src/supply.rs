@@ -0,0 +1,119 @@
+use anchor_lang::prelude::*;
+
+use crate::config::ProgramConfig;
+use crate::events::SupplyCapSet;
+use crate::nft::{WeaponRarity, WeaponType};
+use crate::{seeds, ErrorCode};
+
+#[derive(Accounts)]
+pub struct InitializeWeaponSupply<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    #[account(
+        seeds = [seeds::CONFIG],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + WeaponSupply::INIT_SPACE,
+        seeds = [seeds::WEAPON_SUPPLY],
+        bump,
+    )]
+    pub weapon_supply: Account<'info, WeaponSupply>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Creates the supply registry with every (type, rarity) pair uncapped.
+pub fn initialize_weapon_supply(ctx: Context<InitializeWeaponSupply>) -> Result<()> {
+    let weapon_supply = &mut ctx.accounts.weapon_supply;
+    weapon_supply.minted = [[0; WeaponRarity::COUNT]; WeaponType::COUNT];
+    weapon_supply.caps = [[u32::MAX; WeaponRarity::COUNT]; WeaponType::COUNT];
+    weapon_supply.bump = ctx.bumps.weapon_supply;
+    
+    msg!("Weapon supply registry initialized");
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateWeaponSupply<'info> {
+    pub admin: Signer<'info>,
+    
+    #[account(
+        seeds = [seeds::CONFIG],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::WEAPON_SUPPLY],
+        bump = weapon_supply.bump,
+    )]
+    pub weapon_supply: Account<'info, WeaponSupply>,
+}
+
+/// Sets the most NFTs that can ever be minted for a (type, rarity) pair.
+/// `u32::MAX` lifts the cap. A cap can't be set below what's already minted.
+pub fn set_supply_cap(
+    ctx: Context<UpdateWeaponSupply>,
+    weapon_type: WeaponType,
+    rarity: WeaponRarity,
+    cap: u32,
+) -> Result<()> {
+    let weapon_supply = &mut ctx.accounts.weapon_supply;
+    let minted = weapon_supply.minted(&weapon_type, &rarity);
+    require!(cap >= minted, ErrorCode::InvalidSupplyCap);
+    
+    weapon_supply.caps[weapon_type.index()][rarity.index()] = cap;
+    
+    emit!(SupplyCapSet {
+        weapon_type: weapon_type.clone(),
+        rarity: rarity.clone(),
+        cap,
+        minted,
+    });
+    msg!("Supply cap for {} {} set to {}", rarity.to_string(), weapon_type.to_string(), cap);
+    Ok(())
+}
+
+/// Minted count and cap for every (type, rarity) pair, indexed by
+/// `WeaponType::index` then `WeaponRarity::index`.
+#[account]
+#[derive(InitSpace)]
+pub struct WeaponSupply {
+    pub minted: [[u32; WeaponRarity::COUNT]; WeaponType::COUNT],
+    pub caps: [[u32; WeaponRarity::COUNT]; WeaponType::COUNT],
+    pub bump: u8,
+}
+
+impl WeaponSupply {
+    pub fn minted(&self, weapon_type: &WeaponType, rarity: &WeaponRarity) -> u32 {
+        self.minted[weapon_type.index()][rarity.index()]
+    }
+    
+    pub fn cap(&self, weapon_type: &WeaponType, rarity: &WeaponRarity) -> u32 {
+        self.caps[weapon_type.index()][rarity.index()]
+    }
+    
+    /// NFTs of the pair that can still be minted.
+    pub fn remaining(&self, weapon_type: &WeaponType, rarity: &WeaponRarity) -> u32 {
+        self.cap(weapon_type, rarity)
+            .saturating_sub(self.minted(weapon_type, rarity))
+    }
+    
+    pub fn record_mint(&mut self, weapon_type: &WeaponType, rarity: &WeaponRarity) -> Result<()> {
+        require!(
+            self.remaining(weapon_type, rarity) > 0,
+            ErrorCode::SupplyCapReached
+        );
+        self.minted[weapon_type.index()][rarity.index()] += 1;
+        Ok(())
+    }
+}
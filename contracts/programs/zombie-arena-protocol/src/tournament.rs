@@ -1,17 +1,45 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 
+use crate::attestation;
+use crate::config::{ProgramConfig, FEATURE_SCORE_ATTESTATIONS, PAUSE_GUILD, PAUSE_TOURNAMENT};
+use crate::events::*;
+use crate::{seeds, ErrorCode};
+
+/// Number of ranked players tracked on each tournament leaderboard.
+pub const MAX_LEADERBOARD_ENTRIES: usize = 10;
+
+/// Domain tag prefixed to score attestations signed by the game server.
+pub const SCORE_ATTESTATION_TAG: &[u8] = b"zap:tournament_score";
+
+/// Basis points that a prize schedule must add up to.
+pub const TOTAL_PRIZE_BPS: u64 = 10_000;
+
 #[derive(Accounts)]
-#[instruction(entry_fee: u64, max_players: u32, end_time: i64)]
+#[instruction(
+    entry_fee: u64,
+    min_players: u32,
+    max_players: u32,
+    end_time: i64,
+    prize_schedule: PrizeSchedule,
+)]
 pub struct CreateTournament<'info> {
     #[account(mut)]
     pub organizer: Signer<'info>,
     
+    #[account(
+        seeds = [seeds::CONFIG],
+        bump = config.bump,
+        constraint = !config.is_paused(PAUSE_TOURNAMENT) @ ErrorCode::ProgramPaused,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
     #[account(
         init,
         payer = organizer,
         space = 8 + Tournament::INIT_SPACE,
-        seeds = [b"tournament", organizer.key().as_ref(), &Clock::get()?.unix_timestamp.to_le_bytes()],
+        seeds = [seeds::TOURNAMENT, organizer.key().as_ref(), &Clock::get()?.unix_timestamp.to_le_bytes()],
         bump,
     )]
     pub tournament: Account<'info, Tournament>,
@@ -21,11 +49,20 @@ pub struct CreateTournament<'info> {
         payer = organizer,
         token::mint = mint,
         token::authority = tournament,
-        seeds = [b"prize_pool", tournament.key().as_ref()],
+        seeds = [seeds::PRIZE_POOL, tournament.key().as_ref()],
         bump,
     )]
     pub prize_pool: Account<'info, TokenAccount>,
     
+    #[account(
+        init,
+        payer = organizer,
+        space = 8 + TournamentLeaderboard::INIT_SPACE,
+        seeds = [seeds::TOURNAMENT_LEADERBOARD, tournament.key().as_ref()],
+        bump,
+    )]
+    pub leaderboard: Account<'info, TournamentLeaderboard>,
+    
     pub mint: Account<'info, anchor_spl::token::Mint>,
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
@@ -34,21 +71,49 @@ pub struct CreateTournament<'info> {
 pub fn create_tournament(
     ctx: Context<CreateTournament>,
     entry_fee: u64,
+    min_players: u32,
     max_players: u32,
     end_time: i64,
+    prize_schedule: PrizeSchedule,
 ) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    require!(end_time > now, ErrorCode::InvalidTournamentTime);
+    require!(
+        min_players >= 1 && min_players <= max_players,
+        ErrorCode::InvalidPlayerLimits
+    );
+    
+    let payout_bps = prize_schedule.payout_bps();
+    PrizeSchedule::validate(&payout_bps)?;
+    
     let tournament = &mut ctx.accounts.tournament;
     tournament.organizer = ctx.accounts.organizer.key();
     tournament.entry_fee = entry_fee;
+    tournament.min_players = min_players;
     tournament.max_players = max_players;
     tournament.current_players = 0;
     tournament.prize_pool = 0;
     tournament.status = TournamentStatus::Open;
-    tournament.start_time = Clock::get()?.unix_timestamp;
+    tournament.start_time = now;
     tournament.end_time = end_time;
+    tournament.payout_bps = payout_bps;
     tournament.bump = ctx.bumps.tournament;
     tournament.prize_pool_bump = ctx.bumps.prize_pool;
     
+    let leaderboard = &mut ctx.accounts.leaderboard;
+    leaderboard.tournament = tournament.key();
+    leaderboard.entries = vec![];
+    leaderboard.bump = ctx.bumps.leaderboard;
+    
+    emit!(TournamentCreated {
+        tournament: tournament.key(),
+        organizer: tournament.organizer,
+        entry_fee,
+        min_players,
+        max_players,
+        end_time,
+        payout_bps: tournament.payout_bps.clone(),
+    });
     msg!("Tournament created with entry fee: {} $ZAP", entry_fee);
     Ok(())
 }
@@ -58,6 +123,13 @@ pub struct JoinTournament<'info> {
     #[account(mut)]
     pub player: Signer<'info>,
     
+    #[account(
+        seeds = [seeds::CONFIG],
+        bump = config.bump,
+        constraint = !config.is_paused(PAUSE_TOURNAMENT) @ ErrorCode::ProgramPaused,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
     #[account(
         mut,
         constraint = tournament.status == TournamentStatus::Open,
@@ -69,7 +141,7 @@ pub struct JoinTournament<'info> {
         init,
         payer = player,
         space = 8 + TournamentEntry::INIT_SPACE,
-        seeds = [b"tournament_entry", tournament.key().as_ref(), player.key().as_ref()],
+        seeds = [seeds::TOURNAMENT_ENTRY, tournament.key().as_ref(), player.key().as_ref()],
         bump,
     )]
     pub tournament_entry: Account<'info, TournamentEntry>,
@@ -82,7 +154,7 @@ pub struct JoinTournament<'info> {
     
     #[account(
         mut,
-        seeds = [b"prize_pool", tournament.key().as_ref()],
+        seeds = [seeds::PRIZE_POOL, tournament.key().as_ref()],
         bump = tournament.prize_pool_bump,
     )]
     pub prize_pool: Account<'info, TokenAccount>,
@@ -95,6 +167,9 @@ pub fn join_tournament(ctx: Context<JoinTournament>) -> Result<()> {
     let tournament = &mut ctx.accounts.tournament;
     let entry = &mut ctx.accounts.tournament_entry;
     
+    let now = Clock::get()?.unix_timestamp;
+    require!(now < tournament.end_time, ErrorCode::TournamentOver);
+    
     // Transfer entry fee
     let cpi_accounts = Transfer {
         from: ctx.accounts.player_token_account.to_account_info(),
@@ -113,17 +188,131 @@ pub fn join_tournament(ctx: Context<JoinTournament>) -> Result<()> {
     entry.tournament = tournament.key();
     entry.highest_round = 0;
     entry.total_kills = 0;
-    entry.joined_at = Clock::get()?.unix_timestamp;
+    entry.nonce = 0;
+    entry.joined_at = now;
     entry.bump = ctx.bumps.tournament_entry;
     
+    emit!(TournamentJoined {
+        tournament: tournament.key(),
+        player: entry.player,
+        current_players: tournament.current_players,
+        prize_pool: tournament.prize_pool,
+    });
     msg!("Player joined tournament. Total players: {}", tournament.current_players);
     Ok(())
 }
 
+#[derive(Accounts)]
+pub struct StartTournament<'info> {
+    pub organizer: Signer<'info>,
+    
+    #[account(
+        seeds = [seeds::CONFIG],
+        bump = config.bump,
+        constraint = !config.is_paused(PAUSE_TOURNAMENT) @ ErrorCode::ProgramPaused,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        mut,
+        constraint = tournament.organizer == organizer.key() @ ErrorCode::Unauthorized,
+        constraint = tournament.status == TournamentStatus::Open @ ErrorCode::InvalidTournamentStatus,
+    )]
+    pub tournament: Account<'info, Tournament>,
+}
+
+pub fn start_tournament(ctx: Context<StartTournament>) -> Result<()> {
+    let tournament = &mut ctx.accounts.tournament;
+    
+    let now = Clock::get()?.unix_timestamp;
+    require!(now < tournament.end_time, ErrorCode::TournamentOver);
+    require!(
+        tournament.current_players >= tournament.min_players,
+        ErrorCode::NotEnoughPlayers
+    );
+    
+    tournament.status = TournamentStatus::Active;
+    
+    emit!(TournamentStatusChanged {
+        tournament: tournament.key(),
+        status: TournamentStatus::Active,
+    });
+    msg!("Tournament started with {} players", tournament.current_players);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct EndTournament<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = tournament.status == TournamentStatus::Active @ ErrorCode::InvalidTournamentStatus,
+    )]
+    pub tournament: Account<'info, Tournament>,
+}
+
+pub fn end_tournament(ctx: Context<EndTournament>) -> Result<()> {
+    let tournament = &mut ctx.accounts.tournament;
+    
+    // Anyone can close out a tournament once it has run its course; the
+    // organizer may also end it early.
+    if ctx.accounts.authority.key() != tournament.organizer {
+        let now = Clock::get()?.unix_timestamp;
+        require!(now >= tournament.end_time, ErrorCode::TournamentNotOver);
+    }
+    
+    tournament.status = TournamentStatus::Ended;
+    
+    emit!(TournamentStatusChanged {
+        tournament: tournament.key(),
+        status: TournamentStatus::Ended,
+    });
+    msg!("Tournament ended");
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CancelTournament<'info> {
+    pub organizer: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = tournament.organizer == organizer.key() @ ErrorCode::Unauthorized,
+        constraint = matches!(
+            tournament.status,
+            TournamentStatus::Open | TournamentStatus::Active
+        ) @ ErrorCode::InvalidTournamentStatus,
+    )]
+    pub tournament: Account<'info, Tournament>,
+}
+
+pub fn cancel_tournament(ctx: Context<CancelTournament>) -> Result<()> {
+    let tournament = &mut ctx.accounts.tournament;
+    tournament.status = TournamentStatus::Cancelled;
+    
+    emit!(TournamentStatusChanged {
+        tournament: tournament.key(),
+        status: TournamentStatus::Cancelled,
+    });
+    msg!("Tournament cancelled");
+    Ok(())
+}
+
 #[derive(Accounts)]
 pub struct UpdateTournamentScore<'info> {
-    #[account(mut)]
-    pub player: Signer<'info>,
+    /// The game server itself, or anyone relaying a score it attested to.
+    pub authority: Signer<'info>,
+    
+    /// CHECK: Player whose entry is updated; bound by the entry seeds
+    pub player: UncheckedAccount<'info>,
+    
+    #[account(
+        seeds = [seeds::CONFIG],
+        bump = config.bump,
+        constraint = !config.is_paused(PAUSE_TOURNAMENT) @ ErrorCode::ProgramPaused,
+    )]
+    pub config: Account<'info, ProgramConfig>,
     
     #[account(
         constraint = tournament.status == TournamentStatus::Active,
@@ -132,29 +321,96 @@ pub struct UpdateTournamentScore<'info> {
     
     #[account(
         mut,
-        seeds = [b"tournament_entry", tournament.key().as_ref(), player.key().as_ref()],
+        seeds = [seeds::TOURNAMENT_ENTRY, tournament.key().as_ref(), player.key().as_ref()],
         bump = tournament_entry.bump,
         constraint = tournament_entry.player == player.key(),
     )]
     pub tournament_entry: Account<'info, TournamentEntry>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::TOURNAMENT_LEADERBOARD, tournament.key().as_ref()],
+        bump = leaderboard.bump,
+    )]
+    pub leaderboard: Account<'info, TournamentLeaderboard>,
+    
+    #[account(address = sysvar::instructions::ID)]
+    /// CHECK: Instructions sysvar, read for the ed25519 attestation
+    pub instructions: UncheckedAccount<'info>,
 }
 
 pub fn update_tournament_score(
     ctx: Context<UpdateTournamentScore>,
     round: u32,
     kills: u64,
+    nonce: u64,
 ) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    require!(now < ctx.accounts.tournament.end_time, ErrorCode::TournamentOver);
+    require!(nonce > ctx.accounts.tournament_entry.nonce, ErrorCode::StaleNonce);
+    
+    // Scores either come straight from a game server, or carry its ed25519
+    // signature in the preceding instruction.
+    let config = &ctx.accounts.config;
+    if !config.is_game_server(&ctx.accounts.authority.key()) {
+        require!(
+            config.is_enabled(FEATURE_SCORE_ATTESTATIONS),
+            ErrorCode::FeatureDisabled
+        );
+        let message = score_attestation_message(
+            &ctx.accounts.tournament.key(),
+            &ctx.accounts.player.key(),
+            round,
+            kills,
+            nonce,
+        );
+        let signer = attestation::verify_ed25519_attestation(&ctx.accounts.instructions, &message)?;
+        require!(config.is_game_server(&signer), ErrorCode::Unauthorized);
+    }
+    
     let entry = &mut ctx.accounts.tournament_entry;
     
     if round > entry.highest_round {
         entry.highest_round = round;
     }
     entry.total_kills += kills;
+    entry.nonce = nonce;
     
+    ctx.accounts
+        .leaderboard
+        .record(entry.player, entry.highest_round, entry.total_kills);
+    
+    emit!(TournamentScoreUpdated {
+        tournament: entry.tournament,
+        player: entry.player,
+        round,
+        kills,
+        highest_round: entry.highest_round,
+        total_kills: entry.total_kills,
+        nonce,
+    });
     msg!("Updated tournament score - Round: {}, Total Kills: {}", round, entry.total_kills);
     Ok(())
 }
 
+/// Bytes the game server signs to attest to a tournament score.
+pub fn score_attestation_message(
+    tournament: &Pubkey,
+    player: &Pubkey,
+    round: u32,
+    kills: u64,
+    nonce: u64,
+) -> Vec<u8> {
+    let mut message = Vec::with_capacity(SCORE_ATTESTATION_TAG.len() + 32 + 32 + 4 + 8 + 8);
+    message.extend_from_slice(SCORE_ATTESTATION_TAG);
+    message.extend_from_slice(tournament.as_ref());
+    message.extend_from_slice(player.as_ref());
+    message.extend_from_slice(&round.to_le_bytes());
+    message.extend_from_slice(&kills.to_le_bytes());
+    message.extend_from_slice(&nonce.to_le_bytes());
+    message
+}
+
 #[derive(Accounts)]
 pub struct DistributePrizes<'info> {
     #[account(
@@ -166,29 +422,146 @@ pub struct DistributePrizes<'info> {
     
     pub organizer: Signer<'info>,
     
+    #[account(
+        seeds = [seeds::TOURNAMENT_LEADERBOARD, tournament.key().as_ref()],
+        bump = leaderboard.bump,
+    )]
+    pub leaderboard: Account<'info, TournamentLeaderboard>,
+    
     #[account(
         mut,
-        seeds = [b"prize_pool", tournament.key().as_ref()],
+        seeds = [seeds::PRIZE_POOL, tournament.key().as_ref()],
         bump = tournament.prize_pool_bump,
     )]
     pub prize_pool: Account<'info, TokenAccount>,
     
+    pub token_program: Program<'info, Token>,
+    // remaining_accounts: one $ZAP token account per paid place, in rank
+    // order, each owned by the player holding that place on the leaderboard.
+}
+
+pub fn distribute_prizes<'info>(
+    ctx: Context<'_, '_, 'info, 'info, DistributePrizes<'info>>,
+) -> Result<()> {
+    let tournament = &mut ctx.accounts.tournament;
+    let ranked = &ctx.accounts.leaderboard.entries;
+    
+    require!(!ranked.is_empty(), ErrorCode::NoRankedPlayers);
+    
+    let pool_balance = ctx.accounts.prize_pool.amount;
+    let payouts = tournament.payouts(pool_balance, ranked.len());
+    require!(
+        ctx.remaining_accounts.len() >= payouts.len(),
+        ErrorCode::MissingWinnerAccount
+    );
+    
+    let seeds = &[
+        seeds::TOURNAMENT,
+        tournament.organizer.as_ref(),
+        &tournament.start_time.to_le_bytes(),
+        &[tournament.bump],
+    ];
+    let signer = &[&seeds[..]];
+    
+    for (place, payout) in payouts.iter().enumerate() {
+        let winner_info = &ctx.remaining_accounts[place];
+        let winner_token_account = Account::<TokenAccount>::try_from(winner_info)?;
+        require!(
+            winner_token_account.owner == ranked[place].player
+                && winner_token_account.mint == ctx.accounts.prize_pool.mint,
+            ErrorCode::InvalidWinnerAccount
+        );
+        
+        if *payout == 0 {
+            continue;
+        }
+        
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.prize_pool.to_account_info(),
+            to: winner_info.clone(),
+            authority: tournament.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer,
+        );
+        token::transfer(cpi_ctx, *payout)?;
+        
+        emit!(TournamentPrizePaid {
+            tournament: tournament.key(),
+            player: ranked[place].player,
+            place: place as u8,
+            amount: *payout,
+        });
+        msg!("Place {}: {} $ZAP to {}", place + 1, payout, ranked[place].player);
+    }
+    
+    tournament.status = TournamentStatus::Distributed;
+    
+    emit!(TournamentStatusChanged {
+        tournament: tournament.key(),
+        status: TournamentStatus::Distributed,
+    });
+    emit!(TournamentPrizesDistributed {
+        tournament: tournament.key(),
+        total: pool_balance,
+    });
+    msg!("Distributed {} $ZAP in prizes", pool_balance);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClaimRefund<'info> {
     #[account(mut)]
-    pub winner_token_account: Account<'info, TokenAccount>,
+    pub player: Signer<'info>,
+    
+    #[account(mut)]
+    pub tournament: Account<'info, Tournament>,
+    
+    #[account(
+        mut,
+        close = player,
+        seeds = [seeds::TOURNAMENT_ENTRY, tournament.key().as_ref(), player.key().as_ref()],
+        bump = tournament_entry.bump,
+        constraint = tournament_entry.player == player.key(),
+    )]
+    pub tournament_entry: Account<'info, TournamentEntry>,
+    
+    #[account(
+        seeds = [seeds::TOURNAMENT_LEADERBOARD, tournament.key().as_ref()],
+        bump = leaderboard.bump,
+    )]
+    pub leaderboard: Account<'info, TournamentLeaderboard>,
+    
+    #[account(
+        mut,
+        constraint = player_token_account.owner == player.key(),
+        constraint = player_token_account.mint == prize_pool.mint,
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        seeds = [seeds::PRIZE_POOL, tournament.key().as_ref()],
+        bump = tournament.prize_pool_bump,
+    )]
+    pub prize_pool: Account<'info, TokenAccount>,
     
     pub token_program: Program<'info, Token>,
 }
 
-pub fn distribute_prizes(ctx: Context<DistributePrizes>) -> Result<()> {
+pub fn claim_refund(ctx: Context<ClaimRefund>) -> Result<()> {
     let tournament = &mut ctx.accounts.tournament;
     
-    let first_place_prize = tournament.prize_pool * 50 / 100; // 50%
-    let second_place_prize = tournament.prize_pool * 30 / 100; // 30%
-    let third_place_prize = tournament.prize_pool * 20 / 100; // 20%
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        tournament.is_refundable(now, ctx.accounts.leaderboard.entries.len()),
+        ErrorCode::RefundNotAvailable
+    );
     
-    // Transfer to winner (simplified - in reality would need to track rankings)
     let seeds = &[
-        b"tournament",
+        seeds::TOURNAMENT,
         tournament.organizer.as_ref(),
         &tournament.start_time.to_le_bytes(),
         &[tournament.bump],
@@ -197,7 +570,7 @@ pub fn distribute_prizes(ctx: Context<DistributePrizes>) -> Result<()> {
     
     let cpi_accounts = Transfer {
         from: ctx.accounts.prize_pool.to_account_info(),
-        to: ctx.accounts.winner_token_account.to_account_info(),
+        to: ctx.accounts.player_token_account.to_account_info(),
         authority: tournament.to_account_info(),
     };
     let cpi_ctx = CpiContext::new_with_signer(
@@ -205,11 +578,17 @@ pub fn distribute_prizes(ctx: Context<DistributePrizes>) -> Result<()> {
         cpi_accounts,
         signer,
     );
-    token::transfer(cpi_ctx, first_place_prize)?;
+    token::transfer(cpi_ctx, tournament.entry_fee)?;
     
-    tournament.status = TournamentStatus::Distributed;
+    tournament.prize_pool -= tournament.entry_fee;
+    tournament.current_players -= 1;
     
-    msg!("Distributed {} $ZAP in prizes", tournament.prize_pool);
+    emit!(TournamentRefunded {
+        tournament: tournament.key(),
+        player: ctx.accounts.player.key(),
+        amount: tournament.entry_fee,
+    });
+    msg!("Refunded {} $ZAP entry fee", tournament.entry_fee);
     Ok(())
 }
 
@@ -218,16 +597,52 @@ pub fn distribute_prizes(ctx: Context<DistributePrizes>) -> Result<()> {
 pub struct Tournament {
     pub organizer: Pubkey,
     pub entry_fee: u64,
+    pub min_players: u32,
     pub max_players: u32,
     pub current_players: u32,
     pub prize_pool: u64,
     pub status: TournamentStatus,
     pub start_time: i64,
     pub end_time: i64,
+    #[max_len(MAX_LEADERBOARD_ENTRIES)]
+    pub payout_bps: Vec<u16>,
     pub bump: u8,
     pub prize_pool_bump: u8,
 }
 
+impl Tournament {
+    /// Entry fees can be reclaimed once a tournament is cancelled, when it
+    /// reached its end time without ever filling up enough to start, or when
+    /// it ended without a single accepted score, since `distribute_prizes`
+    /// has nobody to pay then.
+    pub fn is_refundable(&self, now: i64, ranked_players: usize) -> bool {
+        match self.status {
+            TournamentStatus::Cancelled => true,
+            TournamentStatus::Open => now >= self.end_time,
+            TournamentStatus::Ended => ranked_players == 0,
+            _ => false,
+        }
+    }
+    
+    /// Prize for each paid place that has a ranked player. Lower places get
+    /// their exact share of `prize_pool`; first place takes whatever is left
+    /// of `pool_balance` so unfilled places and rounding dust never stay
+    /// locked in the pool.
+    pub fn payouts(&self, pool_balance: u64, ranked_players: usize) -> Vec<u64> {
+        let places = ranked_players.min(self.payout_bps.len());
+        let mut payouts = vec![0u64; places];
+        for (place, payout) in payouts.iter_mut().enumerate().skip(1) {
+            *payout = (self.prize_pool as u128 * self.payout_bps[place] as u128
+                / TOTAL_PRIZE_BPS as u128) as u64;
+        }
+        let lower_places: u64 = payouts.iter().sum();
+        if let Some(first) = payouts.first_mut() {
+            *first = pool_balance.saturating_sub(lower_places);
+        }
+        payouts
+    }
+}
+
 #[account]
 #[derive(InitSpace)]
 pub struct TournamentEntry {
@@ -235,16 +650,109 @@ pub struct TournamentEntry {
     pub tournament: Pubkey,
     pub highest_round: u32,
     pub total_kills: u64,
+    /// Last score nonce accepted for this entry.
+    pub nonce: u64,
     pub joined_at: i64,
     pub bump: u8,
 }
 
+#[account]
+#[derive(InitSpace)]
+pub struct TournamentLeaderboard {
+    pub tournament: Pubkey,
+    #[max_len(MAX_LEADERBOARD_ENTRIES)]
+    pub entries: Vec<LeaderboardEntry>,
+    pub bump: u8,
+}
+
+impl TournamentLeaderboard {
+    /// Places the player's latest score on the board, keeping it sorted by
+    /// `highest_round` then `total_kills`. Ties keep the player who got there
+    /// first ahead.
+    pub fn record(&mut self, player: Pubkey, highest_round: u32, total_kills: u64) {
+        self.entries.retain(|e| e.player != player);
+        
+        let entry = LeaderboardEntry {
+            player,
+            highest_round,
+            total_kills,
+        };
+        let position = self
+            .entries
+            .iter()
+            .position(|e| entry.outranks(e))
+            .unwrap_or(self.entries.len());
+        
+        if position < MAX_LEADERBOARD_ENTRIES {
+            self.entries.insert(position, entry);
+            self.entries.truncate(MAX_LEADERBOARD_ENTRIES);
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, InitSpace)]
+pub struct LeaderboardEntry {
+    pub player: Pubkey,
+    pub highest_round: u32,
+    pub total_kills: u64,
+}
+
+impl LeaderboardEntry {
+    pub fn outranks(&self, other: &LeaderboardEntry) -> bool {
+        (self.highest_round, self.total_kills) > (other.highest_round, other.total_kills)
+    }
+}
+
+/// How the prize pool is split between ranked places, chosen by the organizer
+/// when the tournament is created.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub enum PrizeSchedule {
+    WinnerTakesAll,
+    TopThree,
+    TopTenCurve,
+    /// Basis points per place, first place first.
+    Custom(Vec<u16>),
+}
+
+impl PrizeSchedule {
+    pub fn payout_bps(&self) -> Vec<u16> {
+        match self {
+            PrizeSchedule::WinnerTakesAll => vec![10_000],
+            PrizeSchedule::TopThree => vec![5000, 3000, 2000],
+            PrizeSchedule::TopTenCurve => {
+                vec![2500, 1800, 1400, 1100, 900, 700, 550, 450, 350, 250]
+            }
+            PrizeSchedule::Custom(bps) => bps.clone(),
+        }
+    }
+    
+    /// A payout table must cover between one and `MAX_LEADERBOARD_ENTRIES`
+    /// places, never pay a lower place more than a higher one, and add up to
+    /// exactly 100%.
+    pub fn validate(payout_bps: &[u16]) -> Result<()> {
+        require!(
+            !payout_bps.is_empty() && payout_bps.len() <= MAX_LEADERBOARD_ENTRIES,
+            ErrorCode::InvalidPrizeSchedule
+        );
+        require!(
+            payout_bps.windows(2).all(|pair| pair[0] >= pair[1]),
+            ErrorCode::InvalidPrizeSchedule
+        );
+        require!(
+            payout_bps.iter().map(|bps| *bps as u64).sum::<u64>() == TOTAL_PRIZE_BPS,
+            ErrorCode::InvalidPrizeSchedule
+        );
+        Ok(())
+    }
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, InitSpace)]
 pub enum TournamentStatus {
     Open,
     Active,
     Ended,
     Distributed,
+    Cancelled,
 }
 
 // Guild System
@@ -254,11 +762,18 @@ pub struct CreateGuild<'info> {
     #[account(mut)]
     pub leader: Signer<'info>,
     
+    #[account(
+        seeds = [seeds::CONFIG],
+        bump = config.bump,
+        constraint = !config.is_paused(PAUSE_GUILD) @ ErrorCode::ProgramPaused,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
     #[account(
         init,
         payer = leader,
         space = 8 + Guild::INIT_SPACE,
-        seeds = [b"guild", name.as_bytes()],
+        seeds = [seeds::GUILD, name.as_bytes()],
         bump,
     )]
     pub guild: Account<'info, Guild>,
@@ -268,7 +783,7 @@ pub struct CreateGuild<'info> {
         payer = leader,
         token::mint = mint,
         token::authority = guild,
-        seeds = [b"guild_treasury", guild.key().as_ref()],
+        seeds = [seeds::GUILD_TREASURY, guild.key().as_ref()],
         bump,
     )]
     pub guild_treasury: Account<'info, TokenAccount>,
@@ -293,6 +808,11 @@ pub fn create_guild(
     guild.bump = ctx.bumps.guild;
     guild.treasury_bump = ctx.bumps.guild_treasury;
     
+    emit!(GuildCreated {
+        guild: guild.key(),
+        leader: guild.leader,
+        name: guild.name.clone(),
+    });
     msg!("Guild '{}' created", guild.name);
     Ok(())
 }
@@ -310,4 +830,113 @@ pub struct Guild {
     pub created_at: i64,
     pub bump: u8,
     pub treasury_bump: u8,
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn tournament(prize_pool: u64, schedule: PrizeSchedule) -> Tournament {
+        Tournament {
+            organizer: Pubkey::new_unique(),
+            entry_fee: 0,
+            min_players: 0,
+            max_players: 0,
+            current_players: 0,
+            prize_pool,
+            status: TournamentStatus::Ended,
+            start_time: 0,
+            end_time: 0,
+            payout_bps: schedule.payout_bps(),
+            bump: 0,
+            prize_pool_bump: 0,
+        }
+    }
+    
+    fn leaderboard() -> TournamentLeaderboard {
+        TournamentLeaderboard {
+            tournament: Pubkey::new_unique(),
+            entries: vec![],
+            bump: 0,
+        }
+    }
+    
+    fn players(leaderboard: &TournamentLeaderboard) -> Vec<Pubkey> {
+        leaderboard.entries.iter().map(|e| e.player).collect()
+    }
+    
+    #[test]
+    fn record_ranks_by_round_then_kills() {
+        let (a, b, c) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let mut leaderboard = leaderboard();
+        leaderboard.record(a, 10, 50);
+        leaderboard.record(b, 12, 10);
+        leaderboard.record(c, 10, 80);
+        assert_eq!(players(&leaderboard), vec![b, c, a]);
+    }
+    
+    #[test]
+    fn record_keeps_earlier_player_ahead_on_ties() {
+        let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut leaderboard = leaderboard();
+        leaderboard.record(a, 10, 50);
+        leaderboard.record(b, 10, 50);
+        assert_eq!(players(&leaderboard), vec![a, b]);
+    }
+    
+    #[test]
+    fn record_replaces_the_players_previous_score() {
+        let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut leaderboard = leaderboard();
+        leaderboard.record(a, 10, 50);
+        leaderboard.record(b, 12, 50);
+        leaderboard.record(a, 15, 0);
+        assert_eq!(players(&leaderboard), vec![a, b]);
+        assert_eq!(leaderboard.entries[0].highest_round, 15);
+    }
+    
+    #[test]
+    fn record_drops_scores_below_a_full_board() {
+        let mut leaderboard = leaderboard();
+        for round in 1..=MAX_LEADERBOARD_ENTRIES as u32 {
+            leaderboard.record(Pubkey::new_unique(), round, 0);
+        }
+        let last = Pubkey::new_unique();
+        leaderboard.record(last, 0, 0);
+        assert!(!players(&leaderboard).contains(&last));
+        
+        let best = Pubkey::new_unique();
+        leaderboard.record(best, 100, 0);
+        assert_eq!(leaderboard.entries.len(), MAX_LEADERBOARD_ENTRIES);
+        assert_eq!(leaderboard.entries[0].player, best);
+        assert_eq!(leaderboard.entries.last().unwrap().highest_round, 2);
+    }
+    
+    #[test]
+    fn payouts_split_by_schedule() {
+        let tournament = tournament(1_000, PrizeSchedule::TopThree);
+        assert_eq!(tournament.payouts(1_000, 5), vec![500, 300, 200]);
+    }
+    
+    #[test]
+    fn payouts_give_rounding_dust_to_first_place() {
+        let tournament = tournament(1_001, PrizeSchedule::TopThree);
+        // 1001 * 30% and 1001 * 20% round down to 300 and 200.
+        assert_eq!(tournament.payouts(1_001, 3), vec![501, 300, 200]);
+    }
+    
+    #[test]
+    fn payouts_give_unfilled_places_to_first_place() {
+        let tournament = tournament(1_000, PrizeSchedule::TopThree);
+        assert_eq!(tournament.payouts(1_000, 2), vec![700, 300]);
+        assert_eq!(tournament.payouts(1_000, 1), vec![1_000]);
+        assert!(tournament.payouts(1_000, 0).is_empty());
+    }
+    
+    #[test]
+    fn payouts_pay_out_the_whole_pool_balance() {
+        // Tokens sent to the pool outside of entry fees go to first place.
+        let tournament = tournament(1_000, PrizeSchedule::TopThree);
+        assert_eq!(tournament.payouts(1_050, 3), vec![550, 300, 200]);
+    }
+}
@@ -0,0 +1,189 @@
+//! Fetching and decoding program accounts.
+
+use std::fmt;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::AccountDeserialize;
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_rpc_client_api::client_error::Error as ClientError;
+
+use zombie_arena_protocol::achievements::{Achievement, AchievementBadge};
+use zombie_arena_protocol::challenges::{Challenge, ChallengeCompletion};
+use zombie_arena_protocol::config::ProgramConfig;
+use zombie_arena_protocol::drops::DropTable;
+use zombie_arena_protocol::leaderboard::GlobalLeaderboard;
+use zombie_arena_protocol::leveling::WeaponLevelConfig;
+use zombie_arena_protocol::multisig::{Multisig, Proposal};
+use zombie_arena_protocol::nft::{MintRequest, WeaponData};
+use zombie_arena_protocol::perks::PerkTable;
+use zombie_arena_protocol::season::{Season, SeasonClaim};
+use zombie_arena_protocol::supply::WeaponSupply;
+use zombie_arena_protocol::tournament::{
+    Guild, Tournament, TournamentEntry, TournamentLeaderboard,
+};
+use zombie_arena_protocol::{PlayerState, TokenData};
+
+use crate::pda;
+
+#[derive(Debug)]
+pub enum SdkError {
+    Rpc(Box<ClientError>),
+    /// The account exists but isn't the expected program account.
+    Decode(anchor_lang::error::Error),
+}
+
+impl fmt::Display for SdkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SdkError::Rpc(err) => write!(f, "rpc error: {err}"),
+            SdkError::Decode(err) => write!(f, "failed to decode account: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for SdkError {}
+
+impl From<ClientError> for SdkError {
+    fn from(err: ClientError) -> Self {
+        SdkError::Rpc(Box::new(err))
+    }
+}
+
+impl From<anchor_lang::error::Error> for SdkError {
+    fn from(err: anchor_lang::error::Error) -> Self {
+        SdkError::Decode(err)
+    }
+}
+
+pub type Result<T> = std::result::Result<T, SdkError>;
+
+/// Decodes raw account data, checking the account discriminator.
+pub fn decode<T: AccountDeserialize>(data: &[u8]) -> Result<T> {
+    let mut data = data;
+    Ok(T::try_deserialize(&mut data)?)
+}
+
+pub fn fetch<T: AccountDeserialize>(client: &RpcClient, address: &Pubkey) -> Result<T> {
+    let data = client.get_account_data(address)?;
+    decode(&data)
+}
+
+pub fn fetch_config(client: &RpcClient) -> Result<ProgramConfig> {
+    fetch(client, &pda::config().0)
+}
+
+pub fn fetch_token_data(client: &RpcClient, mint: &Pubkey) -> Result<TokenData> {
+    fetch(client, &pda::token_data(mint).0)
+}
+
+pub fn fetch_player_state(client: &RpcClient, player: &Pubkey) -> Result<PlayerState> {
+    fetch(client, &pda::player_state(player).0)
+}
+
+pub fn fetch_perk_table(client: &RpcClient) -> Result<PerkTable> {
+    fetch(client, &pda::perk_table().0)
+}
+
+pub fn fetch_drop_table(client: &RpcClient) -> Result<DropTable> {
+    fetch(client, &pda::drop_table().0)
+}
+
+pub fn fetch_mystery_box(client: &RpcClient) -> Result<DropTable> {
+    fetch(client, &pda::mystery_box().0)
+}
+
+pub fn fetch_weapon_levels(client: &RpcClient) -> Result<WeaponLevelConfig> {
+    fetch(client, &pda::weapon_levels().0)
+}
+
+/// Minted counts and caps per (type, rarity); see `WeaponSupply::remaining`.
+pub fn fetch_weapon_supply(client: &RpcClient) -> Result<WeaponSupply> {
+    fetch(client, &pda::weapon_supply().0)
+}
+
+pub fn fetch_mint_request(client: &RpcClient, player: &Pubkey) -> Result<MintRequest> {
+    fetch(client, &pda::mint_request(player).0)
+}
+
+pub fn fetch_weapon(client: &RpcClient, weapon_mint: &Pubkey) -> Result<WeaponData> {
+    fetch(client, &pda::weapon(weapon_mint).0)
+}
+
+pub fn fetch_tournament(client: &RpcClient, tournament: &Pubkey) -> Result<Tournament> {
+    fetch(client, tournament)
+}
+
+pub fn fetch_tournament_entry(
+    client: &RpcClient,
+    tournament: &Pubkey,
+    player: &Pubkey,
+) -> Result<TournamentEntry> {
+    fetch(client, &pda::tournament_entry(tournament, player).0)
+}
+
+pub fn fetch_tournament_leaderboard(
+    client: &RpcClient,
+    tournament: &Pubkey,
+) -> Result<TournamentLeaderboard> {
+    fetch(client, &pda::tournament_leaderboard(tournament).0)
+}
+
+pub fn fetch_guild(client: &RpcClient, name: &str) -> Result<Guild> {
+    fetch(client, &pda::guild(name).0)
+}
+
+pub fn fetch_global_leaderboard(client: &RpcClient) -> Result<GlobalLeaderboard> {
+    fetch(client, &pda::global_leaderboard().0)
+}
+
+pub fn fetch_season(client: &RpcClient, season_id: u32) -> Result<Season> {
+    fetch(client, &pda::season(season_id).0)
+}
+
+pub fn fetch_season_claim(
+    client: &RpcClient,
+    season_id: u32,
+    player: &Pubkey,
+) -> Result<SeasonClaim> {
+    let season = pda::season(season_id).0;
+    fetch(client, &pda::season_claim(&season, player).0)
+}
+
+pub fn fetch_achievement(client: &RpcClient, achievement_id: u32) -> Result<Achievement> {
+    fetch(client, &pda::achievement(achievement_id).0)
+}
+
+pub fn fetch_badge(
+    client: &RpcClient,
+    achievement_id: u32,
+    player: &Pubkey,
+) -> Result<AchievementBadge> {
+    let achievement = pda::achievement(achievement_id).0;
+    fetch(client, &pda::badge(&achievement, player).0)
+}
+
+pub fn fetch_challenge(client: &RpcClient, challenge_id: u32) -> Result<Challenge> {
+    fetch(client, &pda::challenge(challenge_id).0)
+}
+
+pub fn fetch_challenge_completion(
+    client: &RpcClient,
+    challenge_id: u32,
+    player: &Pubkey,
+    window_index: u64,
+) -> Result<ChallengeCompletion> {
+    let challenge = pda::challenge(challenge_id).0;
+    fetch(
+        client,
+        &pda::challenge_completion(&challenge, player, window_index).0,
+    )
+}
+
+pub fn fetch_multisig(client: &RpcClient) -> Result<Multisig> {
+    fetch(client, &pda::multisig().0)
+}
+
+pub fn fetch_proposal(client: &RpcClient, index: u64) -> Result<Proposal> {
+    let multisig = pda::multisig().0;
+    fetch(client, &pda::proposal(&multisig, index).0)
+}
@@ -0,0 +1,1071 @@
+//! Instruction builders. Each derives the program addresses an instruction
+//! needs and fills in the fixed program accounts, so callers only pass the
+//! keys that actually vary.
+//!
+//! Instructions that share an accounts struct, such as the admin setters on
+//! `UpdateConfig` or `UpdatePerkTable`, take the program's instruction data
+//! type directly.
+
+use anchor_lang::prelude::{AccountMeta, Pubkey};
+use anchor_lang::solana_program::{instruction::Instruction, system_program, sysvar};
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::associated_token::{self, get_associated_token_address};
+use anchor_spl::metadata::mpl_token_metadata;
+use anchor_spl::token;
+
+use zombie_arena_protocol::achievements::AchievementCriteria;
+use zombie_arena_protocol::challenges::{ChallengeObjective, ChallengeWindow};
+use zombie_arena_protocol::drops::{DropEntry, PricePayment};
+use zombie_arena_protocol::multisig::ProposalAccount;
+use zombie_arena_protocol::nft::{WeaponRarity, WeaponType};
+use zombie_arena_protocol::season::RewardBracket;
+use zombie_arena_protocol::stats::MatchStats;
+use zombie_arena_protocol::tournament::PrizeSchedule;
+use zombie_arena_protocol::{accounts, instruction as ix, ID};
+use zombie_arena_protocol::{EarnReason, EmissionSchedule, PerkType};
+
+use crate::pda;
+
+/// Builds an instruction from any of the program's accounts and instruction
+/// data types. The typed builders below are thin wrappers around this.
+pub fn build(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+// Config
+
+pub fn initialize_config(
+    admin: &Pubkey,
+    game_server: Pubkey,
+    reward_oracle: Pubkey,
+    fee_recipient: Pubkey,
+) -> Instruction {
+    build(
+        accounts::InitializeConfig {
+            admin: *admin,
+            config: pda::config().0,
+            program: ID,
+            program_data: pda::program_data().0,
+            system_program: system_program::ID,
+        },
+        ix::InitializeConfig {
+            game_server,
+            reward_oracle,
+            fee_recipient,
+        },
+    )
+}
+
+/// Any admin instruction on the `UpdateConfig` accounts, e.g.
+/// `ix::AddGameServer` or `ix::SetPaused`.
+pub fn update_config(admin: &Pubkey, data: impl InstructionData) -> Instruction {
+    build(
+        accounts::UpdateConfig {
+            admin: *admin,
+            config: pda::config().0,
+        },
+        data,
+    )
+}
+
+pub fn accept_admin(new_admin: &Pubkey) -> Instruction {
+    build(
+        accounts::AcceptAdmin {
+            new_admin: *new_admin,
+            config: pda::config().0,
+        },
+        ix::AcceptAdmin {},
+    )
+}
+
+// Multisig
+
+pub fn create_multisig(
+    admin: &Pubkey,
+    owners: Vec<Pubkey>,
+    threshold: u8,
+    timelock: i64,
+) -> Instruction {
+    build(
+        accounts::CreateMultisig {
+            admin: *admin,
+            config: pda::config().0,
+            multisig: pda::multisig().0,
+            multisig_signer: pda::multisig_signer().0,
+            system_program: system_program::ID,
+        },
+        ix::CreateMultisig {
+            owners,
+            threshold,
+            timelock,
+        },
+    )
+}
+
+/// `index` must be the multisig's current `proposal_count`.
+pub fn create_proposal(proposer: &Pubkey, index: u64, proposed: &Instruction) -> Instruction {
+    let multisig = pda::multisig().0;
+    build(
+        accounts::CreateProposal {
+            proposer: *proposer,
+            multisig,
+            proposal: pda::proposal(&multisig, index).0,
+            system_program: system_program::ID,
+        },
+        ix::CreateProposal {
+            program_id: proposed.program_id,
+            accounts: proposed
+                .accounts
+                .iter()
+                .map(|meta| ProposalAccount {
+                    pubkey: meta.pubkey,
+                    is_signer: meta.is_signer,
+                    is_writable: meta.is_writable,
+                })
+                .collect(),
+            data: proposed.data.clone(),
+        },
+    )
+}
+
+pub fn approve_proposal(owner: &Pubkey, index: u64) -> Instruction {
+    let multisig = pda::multisig().0;
+    build(
+        accounts::ApproveProposal {
+            owner: *owner,
+            multisig,
+            proposal: pda::proposal(&multisig, index).0,
+        },
+        ix::ApproveProposal {},
+    )
+}
+
+/// `proposed` is the instruction the proposal was created from. Its accounts
+/// are passed along with the multisig signer demoted to a non-signer, since
+/// the program signs for it.
+pub fn execute_proposal(index: u64, proposed: &Instruction) -> Instruction {
+    let multisig = pda::multisig().0;
+    let multisig_signer = pda::multisig_signer().0;
+    let mut instruction = build(
+        accounts::ExecuteProposal {
+            multisig,
+            proposal: pda::proposal(&multisig, index).0,
+            multisig_signer,
+        },
+        ix::ExecuteProposal {},
+    );
+    instruction
+        .accounts
+        .extend(proposed.accounts.iter().map(|meta| AccountMeta {
+            is_signer: meta.is_signer && meta.pubkey != multisig_signer,
+            ..meta.clone()
+        }));
+    instruction
+        .accounts
+        .push(AccountMeta::new_readonly(proposed.program_id, false));
+    instruction
+}
+
+/// `ix::SetMultisigOwners` or `ix::SetMultisigTimelock`, to be wrapped in a
+/// proposal with [`create_proposal`].
+pub fn update_multisig(data: impl InstructionData) -> Instruction {
+    build(
+        accounts::UpdateMultisig {
+            multisig: pda::multisig().0,
+            multisig_signer: pda::multisig_signer().0,
+        },
+        data,
+    )
+}
+
+// $ZAP
+
+/// `mint` is a fresh keypair that must also sign.
+pub fn initialize_token(
+    admin: &Pubkey,
+    mint: &Pubkey,
+    initial_supply: u64,
+    decimals: u8,
+    emission: EmissionSchedule,
+) -> Instruction {
+    build(
+        accounts::InitializeToken {
+            admin: *admin,
+            config: pda::config().0,
+            mint: *mint,
+            token_data: pda::token_data(mint).0,
+            mint_authority: pda::mint_authority(mint).0,
+            admin_token_account: get_associated_token_address(admin, mint),
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+        },
+        ix::InitializeToken {
+            initial_supply,
+            decimals,
+            emission,
+        },
+    )
+}
+
+/// Counts against `token_account_owner`'s daily cap when they are a player.
+pub fn mint_tokens(
+    admin: &Pubkey,
+    mint: &Pubkey,
+    token_account: &Pubkey,
+    token_account_owner: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    build(
+        accounts::MintTokens {
+            admin: *admin,
+            config: pda::config().0,
+            token_data: pda::token_data(mint).0,
+            mint: *mint,
+            mint_authority: pda::mint_authority(mint).0,
+            token_account: *token_account,
+            recipient_state: pda::player_state(token_account_owner).0,
+            token_program: token::ID,
+        },
+        ix::MintTokens { amount },
+    )
+}
+
+/// Without `reward_oracle` co-signing, the transaction needs the oracle's
+/// ed25519 voucher over `earn_voucher_message` right before this instruction.
+pub fn earn_tokens(
+    player: &Pubkey,
+    mint: &Pubkey,
+    player_token_account: &Pubkey,
+    reward_oracle: Option<&Pubkey>,
+    amount: u64,
+    reason: EarnReason,
+    nonce: u64,
+) -> Instruction {
+    build(
+        accounts::EarnTokens {
+            player: *player,
+            player_state: pda::player_state(player).0,
+            config: pda::config().0,
+            reward_oracle: reward_oracle.copied(),
+            token_data: pda::token_data(mint).0,
+            mint: *mint,
+            mint_authority: pda::mint_authority(mint).0,
+            player_token_account: *player_token_account,
+            instructions: sysvar::instructions::ID,
+            token_program: token::ID,
+        },
+        ix::EarnTokens {
+            amount,
+            reason,
+            nonce,
+        },
+    )
+}
+
+// Players and perks
+
+pub fn create_player_state(player: &Pubkey) -> Instruction {
+    build(
+        accounts::CreatePlayerState {
+            player: *player,
+            config: pda::config().0,
+            player_state: pda::player_state(player).0,
+            system_program: system_program::ID,
+        },
+        ix::CreatePlayerState {},
+    )
+}
+
+pub fn initialize_perk_table(admin: &Pubkey) -> Instruction {
+    build(
+        accounts::InitializePerkTable {
+            admin: *admin,
+            config: pda::config().0,
+            perk_table: pda::perk_table().0,
+            system_program: system_program::ID,
+        },
+        ix::InitializePerkTable {},
+    )
+}
+
+/// `ix::SetPerkPrice` or `ix::SetPerkEffect`.
+pub fn update_perk_table(admin: &Pubkey, data: impl InstructionData) -> Instruction {
+    build(
+        accounts::UpdatePerkTable {
+            admin: *admin,
+            config: pda::config().0,
+            perk_table: pda::perk_table().0,
+        },
+        data,
+    )
+}
+
+pub fn burn_for_perk(
+    player: &Pubkey,
+    mint: &Pubkey,
+    player_token_account: &Pubkey,
+    perk_type: PerkType,
+) -> Instruction {
+    build(
+        accounts::BurnForPerk {
+            player: *player,
+            config: pda::config().0,
+            player_state: pda::player_state(player).0,
+            perk_table: pda::perk_table().0,
+            token_data: pda::token_data(mint).0,
+            mint: *mint,
+            player_token_account: *player_token_account,
+            token_program: token::ID,
+        },
+        ix::BurnForPerk { perk_type },
+    )
+}
+
+pub fn deactivate_perk(player: &Pubkey, perk_type: PerkType) -> Instruction {
+    build(
+        accounts::DeactivatePerk {
+            player: *player,
+            config: pda::config().0,
+            perk_table: pda::perk_table().0,
+            player_state: pda::player_state(player).0,
+        },
+        ix::DeactivatePerk { perk_type },
+    )
+}
+
+pub fn consume_revive(game_server: &Pubkey, player: &Pubkey) -> Instruction {
+    build(
+        accounts::ConsumeRevive {
+            game_server: *game_server,
+            config: pda::config().0,
+            perk_table: pda::perk_table().0,
+            player_state: pda::player_state(player).0,
+        },
+        ix::ConsumeRevive {},
+    )
+}
+
+// Stats, seasons, achievements and challenges
+
+pub fn initialize_global_leaderboard(admin: &Pubkey) -> Instruction {
+    build(
+        accounts::InitializeGlobalLeaderboard {
+            admin: *admin,
+            config: pda::config().0,
+            global_leaderboard: pda::global_leaderboard().0,
+            system_program: system_program::ID,
+        },
+        ix::InitializeGlobalLeaderboard {},
+    )
+}
+
+/// Pass the current `season_id` for the match to count towards it.
+pub fn record_match(
+    game_server: &Pubkey,
+    player: &Pubkey,
+    season_id: Option<u32>,
+    stats: MatchStats,
+) -> Instruction {
+    build(
+        accounts::RecordMatch {
+            game_server: *game_server,
+            config: pda::config().0,
+            player_state: pda::player_state(player).0,
+            global_leaderboard: pda::global_leaderboard().0,
+            season: season_id.map(|id| pda::season(id).0),
+        },
+        ix::RecordMatch { stats },
+    )
+}
+
+pub fn start_season(
+    admin: &Pubkey,
+    mint: &Pubkey,
+    season_id: u32,
+    end_time: i64,
+    reward_brackets: Vec<RewardBracket>,
+) -> Instruction {
+    let season = pda::season(season_id).0;
+    build(
+        accounts::StartSeason {
+            admin: *admin,
+            config: pda::config().0,
+            season,
+            reward_pool: pda::season_pool(&season).0,
+            token_data: pda::token_data(mint).0,
+            mint: *mint,
+            token_program: token::ID,
+            system_program: system_program::ID,
+        },
+        ix::StartSeason {
+            season_id,
+            end_time,
+            reward_brackets,
+        },
+    )
+}
+
+pub fn claim_season_reward(
+    player: &Pubkey,
+    season_id: u32,
+    player_token_account: &Pubkey,
+) -> Instruction {
+    let season = pda::season(season_id).0;
+    build(
+        accounts::ClaimSeasonReward {
+            player: *player,
+            season,
+            player_state: pda::player_state(player).0,
+            season_claim: pda::season_claim(&season, player).0,
+            reward_pool: pda::season_pool(&season).0,
+            player_token_account: *player_token_account,
+            token_program: token::ID,
+            system_program: system_program::ID,
+        },
+        ix::ClaimSeasonReward {},
+    )
+}
+
+pub fn create_achievement(
+    admin: &Pubkey,
+    achievement_id: u32,
+    name: String,
+    criteria: AchievementCriteria,
+    zap_reward: u64,
+) -> Instruction {
+    build(
+        accounts::CreateAchievement {
+            admin: *admin,
+            config: pda::config().0,
+            achievement: pda::achievement(achievement_id).0,
+            system_program: system_program::ID,
+        },
+        ix::CreateAchievement {
+            achievement_id,
+            name,
+            criteria,
+            zap_reward,
+        },
+    )
+}
+
+/// Pass `weapon_mint` for achievements whose criteria look at a weapon. The
+/// player must hold it in their associated token account. `reward` is the
+/// $ZAP mint and the player's token account for it, needed only when the
+/// achievement has a $ZAP reward.
+pub fn claim_achievement(
+    player: &Pubkey,
+    achievement_id: u32,
+    weapon_mint: Option<&Pubkey>,
+    reward: Option<(&Pubkey, &Pubkey)>,
+) -> Instruction {
+    let achievement = pda::achievement(achievement_id).0;
+    let badge_mint = pda::badge_mint(&achievement, player).0;
+    build(
+        accounts::ClaimAchievement {
+            player: *player,
+            config: pda::config().0,
+            achievement,
+            player_state: pda::player_state(player).0,
+            weapon_data: weapon_mint.map(|mint| pda::weapon(mint).0),
+            weapon_account: weapon_mint.map(|mint| get_associated_token_address(player, mint)),
+            badge: pda::badge(&achievement, player).0,
+            badge_mint,
+            badge_account: get_associated_token_address(player, &badge_mint),
+            badge_authority: pda::nft_mint_authority().0,
+            token_data: reward.map(|(zap_mint, _)| pda::token_data(zap_mint).0),
+            zap_mint: reward.map(|(zap_mint, _)| *zap_mint),
+            zap_mint_authority: reward.map(|(zap_mint, _)| pda::mint_authority(zap_mint).0),
+            player_token_account: reward.map(|(_, token_account)| *token_account),
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+        },
+        ix::ClaimAchievement {},
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn create_challenge(
+    admin: &Pubkey,
+    challenge_id: u32,
+    objective: ChallengeObjective,
+    target: u64,
+    reward: u64,
+    window: ChallengeWindow,
+    start_time: i64,
+    end_time: i64,
+) -> Instruction {
+    build(
+        accounts::CreateChallenge {
+            admin: *admin,
+            config: pda::config().0,
+            challenge: pda::challenge(challenge_id).0,
+            system_program: system_program::ID,
+        },
+        ix::CreateChallenge {
+            challenge_id,
+            objective,
+            target,
+            reward,
+            window,
+            start_time,
+            end_time,
+        },
+    )
+}
+
+pub fn complete_challenge(
+    game_server: &Pubkey,
+    challenge_id: u32,
+    player: &Pubkey,
+    window_index: u64,
+    mint: &Pubkey,
+    player_token_account: &Pubkey,
+) -> Instruction {
+    let challenge = pda::challenge(challenge_id).0;
+    build(
+        accounts::CompleteChallenge {
+            game_server: *game_server,
+            config: pda::config().0,
+            challenge,
+            player_state: pda::player_state(player).0,
+            completion: pda::challenge_completion(&challenge, player, window_index).0,
+            token_data: pda::token_data(mint).0,
+            mint: *mint,
+            mint_authority: pda::mint_authority(mint).0,
+            player_token_account: *player_token_account,
+            token_program: token::ID,
+            system_program: system_program::ID,
+        },
+        ix::CompleteChallenge { window_index },
+    )
+}
+
+// Weapons
+
+pub fn initialize_drop_table(
+    admin: &Pubkey,
+    price: u64,
+    payment: PricePayment,
+    entries: Vec<DropEntry>,
+) -> Instruction {
+    build(
+        accounts::InitializeDropTable {
+            admin: *admin,
+            config: pda::config().0,
+            drop_table: pda::drop_table().0,
+            system_program: system_program::ID,
+        },
+        ix::InitializeDropTable {
+            price,
+            payment,
+            entries,
+        },
+    )
+}
+
+pub fn set_drop_table(
+    admin: &Pubkey,
+    price: u64,
+    payment: PricePayment,
+    entries: Vec<DropEntry>,
+) -> Instruction {
+    build(
+        accounts::UpdateDropTable {
+            admin: *admin,
+            config: pda::config().0,
+            drop_table: pda::drop_table().0,
+        },
+        ix::SetDropTable {
+            price,
+            payment,
+            entries,
+        },
+    )
+}
+
+pub fn initialize_mystery_box(
+    admin: &Pubkey,
+    price: u64,
+    payment: PricePayment,
+    entries: Vec<DropEntry>,
+) -> Instruction {
+    build(
+        accounts::InitializeMysteryBox {
+            admin: *admin,
+            config: pda::config().0,
+            mystery_box: pda::mystery_box().0,
+            system_program: system_program::ID,
+        },
+        ix::InitializeMysteryBox {
+            price,
+            payment,
+            entries,
+        },
+    )
+}
+
+pub fn set_mystery_box(
+    admin: &Pubkey,
+    price: u64,
+    payment: PricePayment,
+    entries: Vec<DropEntry>,
+) -> Instruction {
+    build(
+        accounts::UpdateMysteryBox {
+            admin: *admin,
+            config: pda::config().0,
+            mystery_box: pda::mystery_box().0,
+        },
+        ix::SetMysteryBox {
+            price,
+            payment,
+            entries,
+        },
+    )
+}
+
+/// Commits to a weapon roll, escrowing the drop table's price. Reveal it with
+/// [`mint_weapon_nft`] from the next slot on.
+pub fn request_weapon_mint(
+    player: &Pubkey,
+    zap_mint: &Pubkey,
+    player_token_account: &Pubkey,
+) -> Instruction {
+    let mint_request = pda::mint_request(player).0;
+    build(
+        accounts::RequestWeaponMint {
+            player: *player,
+            config: pda::config().0,
+            drop_table: pda::drop_table().0,
+            weapon_supply: pda::weapon_supply().0,
+            mint_request,
+            escrow: pda::mint_escrow(&mint_request).0,
+            zap_mint: *zap_mint,
+            token_data: pda::token_data(zap_mint).0,
+            player_token_account: *player_token_account,
+            token_program: token::ID,
+            system_program: system_program::ID,
+        },
+        ix::RequestWeaponMint {},
+    )
+}
+
+/// Like [`request_weapon_mint`], but rolls on the mystery box table.
+pub fn open_mystery_box(
+    player: &Pubkey,
+    zap_mint: &Pubkey,
+    player_token_account: &Pubkey,
+) -> Instruction {
+    let mint_request = pda::mint_request(player).0;
+    build(
+        accounts::OpenMysteryBox {
+            player: *player,
+            config: pda::config().0,
+            mystery_box: pda::mystery_box().0,
+            weapon_supply: pda::weapon_supply().0,
+            mint_request,
+            escrow: pda::mint_escrow(&mint_request).0,
+            zap_mint: *zap_mint,
+            token_data: pda::token_data(zap_mint).0,
+            player_token_account: *player_token_account,
+            token_program: token::ID,
+            system_program: system_program::ID,
+        },
+        ix::OpenMysteryBox {},
+    )
+}
+
+/// Reveals the player's pending mint request. `drop_table` is the table the
+/// request was made against: [`pda::drop_table`] or [`pda::mystery_box`].
+/// `weapon_mint` is a fresh keypair that must also sign.
+///
+/// `fee_recipient_account` is the fee recipient's $ZAP account, needed when
+/// the request's price is paid to it rather than burned.
+pub fn mint_weapon_nft(
+    player: &Pubkey,
+    drop_table: &Pubkey,
+    weapon_mint: &Pubkey,
+    zap_mint: &Pubkey,
+    fee_recipient_account: Option<Pubkey>,
+) -> Instruction {
+    let mint_request = pda::mint_request(player).0;
+    build(
+        accounts::MintWeaponNFT {
+            player: *player,
+            config: pda::config().0,
+            mint_request,
+            drop_table: *drop_table,
+            slot_hashes: sysvar::slot_hashes::ID,
+            weapon_supply: pda::weapon_supply().0,
+            escrow: pda::mint_escrow(&mint_request).0,
+            token_data: pda::token_data(zap_mint).0,
+            zap_mint: *zap_mint,
+            fee_recipient_account,
+            weapon_mint: *weapon_mint,
+            weapon_account: get_associated_token_address(player, weapon_mint),
+            metadata_account: pda::weapon_metadata(weapon_mint).0,
+            weapon_data: pda::weapon(weapon_mint).0,
+            mint_authority: pda::nft_mint_authority().0,
+            metaplex_program: mpl_token_metadata::ID,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+            rent: sysvar::rent::ID,
+        },
+        ix::MintWeaponNft {},
+    )
+}
+
+pub fn initialize_weapon_supply(admin: &Pubkey) -> Instruction {
+    build(
+        accounts::InitializeWeaponSupply {
+            admin: *admin,
+            config: pda::config().0,
+            weapon_supply: pda::weapon_supply().0,
+            system_program: system_program::ID,
+        },
+        ix::InitializeWeaponSupply {},
+    )
+}
+
+/// `cap` of `u32::MAX` leaves the pair uncapped.
+pub fn set_supply_cap(
+    admin: &Pubkey,
+    weapon_type: WeaponType,
+    rarity: WeaponRarity,
+    cap: u32,
+) -> Instruction {
+    build(
+        accounts::UpdateWeaponSupply {
+            admin: *admin,
+            config: pda::config().0,
+            weapon_supply: pda::weapon_supply().0,
+        },
+        ix::SetSupplyCap {
+            weapon_type,
+            rarity,
+            cap,
+        },
+    )
+}
+
+/// Closes a mint request that can no longer be revealed. The escrowed price
+/// is settled, not refunded.
+pub fn close_expired_mint_request(
+    player: &Pubkey,
+    zap_mint: &Pubkey,
+    fee_recipient_account: Option<Pubkey>,
+) -> Instruction {
+    let mint_request = pda::mint_request(player).0;
+    build(
+        accounts::CloseMintRequest {
+            player: *player,
+            config: pda::config().0,
+            mint_request,
+            escrow: pda::mint_escrow(&mint_request).0,
+            token_data: pda::token_data(zap_mint).0,
+            zap_mint: *zap_mint,
+            fee_recipient_account,
+            token_program: token::ID,
+        },
+        ix::CloseExpiredMintRequest {},
+    )
+}
+
+/// Refunds a request whose roll landed on a sold-out weapon.
+pub fn refund_sold_out_mint_request(
+    player: &Pubkey,
+    drop_table: &Pubkey,
+    player_token_account: &Pubkey,
+) -> Instruction {
+    let mint_request = pda::mint_request(player).0;
+    build(
+        accounts::RefundSoldOutMintRequest {
+            player: *player,
+            mint_request,
+            drop_table: *drop_table,
+            slot_hashes: sysvar::slot_hashes::ID,
+            weapon_supply: pda::weapon_supply().0,
+            escrow: pda::mint_escrow(&mint_request).0,
+            player_token_account: *player_token_account,
+            token_program: token::ID,
+        },
+        ix::RefundSoldOutMintRequest {},
+    )
+}
+
+pub fn pack_a_punch_weapon(
+    player: &Pubkey,
+    weapon_mint: &Pubkey,
+    zap_mint: &Pubkey,
+    player_token_account: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::PackAPunchWeapon {
+            player: *player,
+            config: pda::config().0,
+            weapon_data: pda::weapon(weapon_mint).0,
+            weapon_mint: *weapon_mint,
+            weapon_account: get_associated_token_address(player, weapon_mint),
+            player_token_account: *player_token_account,
+            zap_mint: *zap_mint,
+            token_data: pda::token_data(zap_mint).0,
+            token_program: token::ID,
+        },
+        ix::PackAPunchWeapon {},
+    )
+}
+
+pub fn initialize_weapon_levels(
+    admin: &Pubkey,
+    xp_thresholds: Vec<u64>,
+    damage_growth_bps: u16,
+) -> Instruction {
+    build(
+        accounts::InitializeWeaponLevels {
+            admin: *admin,
+            config: pda::config().0,
+            weapon_levels: pda::weapon_levels().0,
+            system_program: system_program::ID,
+        },
+        ix::InitializeWeaponLevels {
+            xp_thresholds,
+            damage_growth_bps,
+        },
+    )
+}
+
+pub fn set_weapon_levels(
+    admin: &Pubkey,
+    xp_thresholds: Vec<u64>,
+    damage_growth_bps: u16,
+) -> Instruction {
+    build(
+        accounts::UpdateWeaponLevels {
+            admin: *admin,
+            config: pda::config().0,
+            weapon_levels: pda::weapon_levels().0,
+        },
+        ix::SetWeaponLevels {
+            xp_thresholds,
+            damage_growth_bps,
+        },
+    )
+}
+
+pub fn record_weapon_kills(
+    game_server: &Pubkey,
+    weapon_mint: &Pubkey,
+    kills: u64,
+    xp: u64,
+) -> Instruction {
+    build(
+        accounts::RecordWeaponKills {
+            game_server: *game_server,
+            config: pda::config().0,
+            weapon_levels: pda::weapon_levels().0,
+            weapon_data: pda::weapon(weapon_mint).0,
+        },
+        ix::RecordWeaponKills { kills, xp },
+    )
+}
+
+/// `weapon_account` is the token account currently holding the NFT. Anyone
+/// can send this.
+pub fn sync_weapon_owner(weapon_mint: &Pubkey, weapon_account: &Pubkey) -> Instruction {
+    build(
+        accounts::SyncWeaponOwner {
+            weapon_data: pda::weapon(weapon_mint).0,
+            weapon_account: *weapon_account,
+        },
+        ix::SyncWeaponOwner {},
+    )
+}
+
+// Tournaments and guilds
+
+/// The tournament address is derived from the cluster time the instruction
+/// executes at, so `start_time` has to match the slot it lands in.
+#[allow(clippy::too_many_arguments)]
+pub fn create_tournament(
+    organizer: &Pubkey,
+    mint: &Pubkey,
+    start_time: i64,
+    entry_fee: u64,
+    min_players: u32,
+    max_players: u32,
+    end_time: i64,
+    prize_schedule: PrizeSchedule,
+) -> Instruction {
+    let tournament = pda::tournament(organizer, start_time).0;
+    build(
+        accounts::CreateTournament {
+            organizer: *organizer,
+            config: pda::config().0,
+            tournament,
+            prize_pool: pda::prize_pool(&tournament).0,
+            leaderboard: pda::tournament_leaderboard(&tournament).0,
+            mint: *mint,
+            token_program: token::ID,
+            system_program: system_program::ID,
+        },
+        ix::CreateTournament {
+            entry_fee,
+            min_players,
+            max_players,
+            end_time,
+            prize_schedule,
+        },
+    )
+}
+
+pub fn join_tournament(
+    player: &Pubkey,
+    tournament: &Pubkey,
+    player_token_account: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::JoinTournament {
+            player: *player,
+            config: pda::config().0,
+            tournament: *tournament,
+            tournament_entry: pda::tournament_entry(tournament, player).0,
+            player_token_account: *player_token_account,
+            prize_pool: pda::prize_pool(tournament).0,
+            token_program: token::ID,
+            system_program: system_program::ID,
+        },
+        ix::JoinTournament {},
+    )
+}
+
+pub fn start_tournament(organizer: &Pubkey, tournament: &Pubkey) -> Instruction {
+    build(
+        accounts::StartTournament {
+            organizer: *organizer,
+            config: pda::config().0,
+            tournament: *tournament,
+        },
+        ix::StartTournament {},
+    )
+}
+
+pub fn end_tournament(authority: &Pubkey, tournament: &Pubkey) -> Instruction {
+    build(
+        accounts::EndTournament {
+            authority: *authority,
+            tournament: *tournament,
+        },
+        ix::EndTournament {},
+    )
+}
+
+pub fn cancel_tournament(organizer: &Pubkey, tournament: &Pubkey) -> Instruction {
+    build(
+        accounts::CancelTournament {
+            organizer: *organizer,
+            tournament: *tournament,
+        },
+        ix::CancelTournament {},
+    )
+}
+
+/// `authority` is a game server, or anyone relaying a score attestation in
+/// the preceding ed25519 instruction.
+pub fn update_tournament_score(
+    authority: &Pubkey,
+    tournament: &Pubkey,
+    player: &Pubkey,
+    round: u32,
+    kills: u64,
+    nonce: u64,
+) -> Instruction {
+    build(
+        accounts::UpdateTournamentScore {
+            authority: *authority,
+            player: *player,
+            config: pda::config().0,
+            tournament: *tournament,
+            tournament_entry: pda::tournament_entry(tournament, player).0,
+            leaderboard: pda::tournament_leaderboard(tournament).0,
+            instructions: sysvar::instructions::ID,
+        },
+        ix::UpdateTournamentScore {
+            round,
+            kills,
+            nonce,
+        },
+    )
+}
+
+/// `winner_token_accounts` are the ranked players' $ZAP accounts, first
+/// place first.
+pub fn distribute_prizes(
+    organizer: &Pubkey,
+    tournament: &Pubkey,
+    winner_token_accounts: &[Pubkey],
+) -> Instruction {
+    let mut instruction = build(
+        accounts::DistributePrizes {
+            tournament: *tournament,
+            organizer: *organizer,
+            leaderboard: pda::tournament_leaderboard(tournament).0,
+            prize_pool: pda::prize_pool(tournament).0,
+            token_program: token::ID,
+        },
+        ix::DistributePrizes {},
+    );
+    instruction.accounts.extend(
+        winner_token_accounts
+            .iter()
+            .map(|account| AccountMeta::new(*account, false)),
+    );
+    instruction
+}
+
+pub fn claim_refund(
+    player: &Pubkey,
+    tournament: &Pubkey,
+    player_token_account: &Pubkey,
+) -> Instruction {
+    build(
+        accounts::ClaimRefund {
+            player: *player,
+            tournament: *tournament,
+            tournament_entry: pda::tournament_entry(tournament, player).0,
+            leaderboard: pda::tournament_leaderboard(tournament).0,
+            player_token_account: *player_token_account,
+            prize_pool: pda::prize_pool(tournament).0,
+            token_program: token::ID,
+        },
+        ix::ClaimRefund {},
+    )
+}
+
+pub fn create_guild(
+    leader: &Pubkey,
+    mint: &Pubkey,
+    name: String,
+    description: String,
+) -> Instruction {
+    let guild = pda::guild(&name).0;
+    build(
+        accounts::CreateGuild {
+            leader: *leader,
+            config: pda::config().0,
+            guild,
+            guild_treasury: pda::guild_treasury(&guild).0,
+            mint: *mint,
+            token_program: token::ID,
+            system_program: system_program::ID,
+        },
+        ix::CreateGuild { name, description },
+    )
+}
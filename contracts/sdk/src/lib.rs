@@ -0,0 +1,20 @@
+//! Rust client for the Zombie Arena Protocol program.
+//!
+//! Everything here is built on the program crate itself: instructions are
+//! assembled from its generated `accounts` and `instruction` types, and
+//! accounts decode into its account structs, so a change to the program that
+//! breaks a client call breaks this crate's build rather than a transaction.
+//!
+//! - [`pda`] derives every program address from its seeds.
+//! - [`instructions`] builds ready-to-sign [`Instruction`]s.
+//! - [`accounts`] fetches and decodes program accounts.
+//!
+//! [`Instruction`]: anchor_lang::solana_program::instruction::Instruction
+
+pub mod accounts;
+pub mod instructions;
+pub mod pda;
+
+pub use zombie_arena_protocol::{self as program, ID};
+
+pub use accounts::SdkError;
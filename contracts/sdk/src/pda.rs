@@ -0,0 +1,469 @@
+//! Program derived addresses, one function per seed layout the program uses,
+//! built from the program's `seeds` constants. Each returns the address and
+//! its bump, like `Pubkey::find_program_address`.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+use anchor_spl::metadata::mpl_token_metadata;
+
+use crate::{program::seeds, ID};
+
+fn find(seeds: &[&[u8]]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(seeds, &ID)
+}
+
+/// The program's upgradeable-loader data account, which `initialize_config`
+/// reads the upgrade authority from.
+pub fn program_data() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ID.as_ref()], &bpf_loader_upgradeable::ID)
+}
+
+pub fn config() -> (Pubkey, u8) {
+    find(&[seeds::CONFIG])
+}
+
+pub fn token_data(mint: &Pubkey) -> (Pubkey, u8) {
+    find(&[seeds::TOKEN_DATA, mint.as_ref()])
+}
+
+/// Mint authority of the $ZAP mint `mint`.
+pub fn mint_authority(mint: &Pubkey) -> (Pubkey, u8) {
+    find(&[seeds::MINT_AUTHORITY, mint.as_ref()])
+}
+
+/// Mint and freeze authority of weapon NFTs and achievement badges.
+pub fn nft_mint_authority() -> (Pubkey, u8) {
+    find(&[seeds::MINT_AUTHORITY])
+}
+
+pub fn player_state(player: &Pubkey) -> (Pubkey, u8) {
+    find(&[seeds::PLAYER_STATE, player.as_ref()])
+}
+
+pub fn drop_table() -> (Pubkey, u8) {
+    find(&[seeds::DROP_TABLE])
+}
+
+pub fn mystery_box() -> (Pubkey, u8) {
+    find(&[seeds::MYSTERY_BOX])
+}
+
+pub fn weapon_levels() -> (Pubkey, u8) {
+    find(&[seeds::WEAPON_LEVELS])
+}
+
+pub fn weapon_supply() -> (Pubkey, u8) {
+    find(&[seeds::WEAPON_SUPPLY])
+}
+
+pub fn mint_request(player: &Pubkey) -> (Pubkey, u8) {
+    find(&[seeds::MINT_REQUEST, player.as_ref()])
+}
+
+/// Token account holding a mint request's price until it's revealed.
+pub fn mint_escrow(mint_request: &Pubkey) -> (Pubkey, u8) {
+    find(&[seeds::MINT_ESCROW, mint_request.as_ref()])
+}
+
+pub fn weapon(weapon_mint: &Pubkey) -> (Pubkey, u8) {
+    find(&[seeds::WEAPON, weapon_mint.as_ref()])
+}
+
+/// Metaplex metadata account of a weapon NFT.
+pub fn weapon_metadata(weapon_mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            b"metadata",
+            mpl_token_metadata::ID.as_ref(),
+            weapon_mint.as_ref(),
+        ],
+        &mpl_token_metadata::ID,
+    )
+}
+
+/// Tournaments are keyed by the cluster time `create_tournament` ran at.
+pub fn tournament(organizer: &Pubkey, start_time: i64) -> (Pubkey, u8) {
+    find(&[
+        seeds::TOURNAMENT,
+        organizer.as_ref(),
+        &start_time.to_le_bytes(),
+    ])
+}
+
+pub fn tournament_entry(tournament: &Pubkey, player: &Pubkey) -> (Pubkey, u8) {
+    find(&[
+        seeds::TOURNAMENT_ENTRY,
+        tournament.as_ref(),
+        player.as_ref(),
+    ])
+}
+
+pub fn prize_pool(tournament: &Pubkey) -> (Pubkey, u8) {
+    find(&[seeds::PRIZE_POOL, tournament.as_ref()])
+}
+
+pub fn tournament_leaderboard(tournament: &Pubkey) -> (Pubkey, u8) {
+    find(&[seeds::TOURNAMENT_LEADERBOARD, tournament.as_ref()])
+}
+
+pub fn guild(name: &str) -> (Pubkey, u8) {
+    find(&[seeds::GUILD, name.as_bytes()])
+}
+
+pub fn guild_treasury(guild: &Pubkey) -> (Pubkey, u8) {
+    find(&[seeds::GUILD_TREASURY, guild.as_ref()])
+}
+
+pub fn perk_table() -> (Pubkey, u8) {
+    find(&[seeds::PERK_TABLE])
+}
+
+pub fn global_leaderboard() -> (Pubkey, u8) {
+    find(&[seeds::GLOBAL_LEADERBOARD])
+}
+
+pub fn multisig() -> (Pubkey, u8) {
+    find(&[seeds::MULTISIG])
+}
+
+pub fn multisig_signer() -> (Pubkey, u8) {
+    find(&[seeds::MULTISIG_SIGNER])
+}
+
+pub fn proposal(multisig: &Pubkey, index: u64) -> (Pubkey, u8) {
+    find(&[seeds::PROPOSAL, multisig.as_ref(), &index.to_le_bytes()])
+}
+
+pub fn season(season_id: u32) -> (Pubkey, u8) {
+    find(&[seeds::SEASON, &season_id.to_le_bytes()])
+}
+
+pub fn season_pool(season: &Pubkey) -> (Pubkey, u8) {
+    find(&[seeds::SEASON_POOL, season.as_ref()])
+}
+
+pub fn season_claim(season: &Pubkey, player: &Pubkey) -> (Pubkey, u8) {
+    find(&[seeds::SEASON_CLAIM, season.as_ref(), player.as_ref()])
+}
+
+pub fn achievement(achievement_id: u32) -> (Pubkey, u8) {
+    find(&[seeds::ACHIEVEMENT, &achievement_id.to_le_bytes()])
+}
+
+pub fn badge(achievement: &Pubkey, player: &Pubkey) -> (Pubkey, u8) {
+    find(&[seeds::BADGE, achievement.as_ref(), player.as_ref()])
+}
+
+pub fn badge_mint(achievement: &Pubkey, player: &Pubkey) -> (Pubkey, u8) {
+    find(&[seeds::BADGE_MINT, achievement.as_ref(), player.as_ref()])
+}
+
+pub fn challenge(challenge_id: u32) -> (Pubkey, u8) {
+    find(&[seeds::CHALLENGE, &challenge_id.to_le_bytes()])
+}
+
+pub fn challenge_completion(
+    challenge: &Pubkey,
+    player: &Pubkey,
+    window_index: u64,
+) -> (Pubkey, u8) {
+    find(&[
+        seeds::CHALLENGE_COMPLETION,
+        challenge.as_ref(),
+        player.as_ref(),
+        &window_index.to_le_bytes(),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expected(seeds: &[&[u8]]) -> (Pubkey, u8) {
+        Pubkey::find_program_address(seeds, &crate::ID)
+    }
+
+    #[test]
+    fn program_data_is_derived_by_the_upgradeable_loader() {
+        assert_eq!(
+            program_data(),
+            Pubkey::find_program_address(&[crate::ID.as_ref()], &bpf_loader_upgradeable::ID)
+        );
+    }
+
+    #[test]
+    fn config_matches_program_seeds() {
+        assert_eq!(config(), expected(&[seeds::CONFIG]));
+    }
+
+    #[test]
+    fn token_data_matches_program_seeds() {
+        let mint = Pubkey::new_unique();
+        assert_eq!(
+            token_data(&mint),
+            expected(&[seeds::TOKEN_DATA, mint.as_ref()])
+        );
+    }
+
+    #[test]
+    fn mint_authority_matches_program_seeds() {
+        let mint = Pubkey::new_unique();
+        assert_eq!(
+            mint_authority(&mint),
+            expected(&[seeds::MINT_AUTHORITY, mint.as_ref()])
+        );
+    }
+
+    #[test]
+    fn nft_mint_authority_matches_program_seeds() {
+        assert_eq!(nft_mint_authority(), expected(&[seeds::MINT_AUTHORITY]));
+        assert_ne!(nft_mint_authority(), mint_authority(&Pubkey::new_unique()));
+    }
+
+    #[test]
+    fn player_state_matches_program_seeds() {
+        let player = Pubkey::new_unique();
+        assert_eq!(
+            player_state(&player),
+            expected(&[seeds::PLAYER_STATE, player.as_ref()])
+        );
+    }
+
+    #[test]
+    fn drop_table_matches_program_seeds() {
+        assert_eq!(drop_table(), expected(&[seeds::DROP_TABLE]));
+    }
+
+    #[test]
+    fn mystery_box_matches_program_seeds() {
+        assert_eq!(mystery_box(), expected(&[seeds::MYSTERY_BOX]));
+    }
+
+    #[test]
+    fn weapon_levels_matches_program_seeds() {
+        assert_eq!(weapon_levels(), expected(&[seeds::WEAPON_LEVELS]));
+    }
+
+    #[test]
+    fn weapon_supply_matches_program_seeds() {
+        assert_eq!(weapon_supply(), expected(&[seeds::WEAPON_SUPPLY]));
+    }
+
+    #[test]
+    fn mint_request_matches_program_seeds() {
+        let player = Pubkey::new_unique();
+        assert_eq!(
+            mint_request(&player),
+            expected(&[seeds::MINT_REQUEST, player.as_ref()])
+        );
+    }
+
+    #[test]
+    fn mint_escrow_matches_program_seeds() {
+        let mint_request = Pubkey::new_unique();
+        assert_eq!(
+            mint_escrow(&mint_request),
+            expected(&[seeds::MINT_ESCROW, mint_request.as_ref()])
+        );
+    }
+
+    #[test]
+    fn weapon_matches_program_seeds() {
+        let weapon_mint = Pubkey::new_unique();
+        assert_eq!(
+            weapon(&weapon_mint),
+            expected(&[seeds::WEAPON, weapon_mint.as_ref()])
+        );
+    }
+
+    #[test]
+    fn weapon_metadata_is_derived_by_token_metadata() {
+        let weapon_mint = Pubkey::new_unique();
+        assert_eq!(
+            weapon_metadata(&weapon_mint),
+            Pubkey::find_program_address(
+                &[
+                    b"metadata",
+                    mpl_token_metadata::ID.as_ref(),
+                    weapon_mint.as_ref(),
+                ],
+                &mpl_token_metadata::ID,
+            )
+        );
+    }
+
+    #[test]
+    fn tournament_matches_program_seeds() {
+        let organizer = Pubkey::new_unique();
+        let start_time: i64 = 1_700_000_000;
+        assert_eq!(
+            tournament(&organizer, start_time),
+            expected(&[
+                seeds::TOURNAMENT,
+                organizer.as_ref(),
+                &start_time.to_le_bytes(),
+            ])
+        );
+    }
+
+    #[test]
+    fn tournament_entry_matches_program_seeds() {
+        let tournament = Pubkey::new_unique();
+        let player = Pubkey::new_unique();
+        assert_eq!(
+            tournament_entry(&tournament, &player),
+            expected(&[
+                seeds::TOURNAMENT_ENTRY,
+                tournament.as_ref(),
+                player.as_ref(),
+            ])
+        );
+    }
+
+    #[test]
+    fn prize_pool_matches_program_seeds() {
+        let tournament = Pubkey::new_unique();
+        assert_eq!(
+            prize_pool(&tournament),
+            expected(&[seeds::PRIZE_POOL, tournament.as_ref()])
+        );
+    }
+
+    #[test]
+    fn tournament_leaderboard_matches_program_seeds() {
+        let tournament = Pubkey::new_unique();
+        assert_eq!(
+            tournament_leaderboard(&tournament),
+            expected(&[seeds::TOURNAMENT_LEADERBOARD, tournament.as_ref()])
+        );
+    }
+
+    #[test]
+    fn guild_matches_program_seeds() {
+        assert_eq!(
+            guild("Der Riese"),
+            expected(&[seeds::GUILD, "Der Riese".as_bytes()])
+        );
+    }
+
+    #[test]
+    fn guild_treasury_matches_program_seeds() {
+        let guild = Pubkey::new_unique();
+        assert_eq!(
+            guild_treasury(&guild),
+            expected(&[seeds::GUILD_TREASURY, guild.as_ref()])
+        );
+    }
+
+    #[test]
+    fn perk_table_matches_program_seeds() {
+        assert_eq!(perk_table(), expected(&[seeds::PERK_TABLE]));
+    }
+
+    #[test]
+    fn global_leaderboard_matches_program_seeds() {
+        assert_eq!(global_leaderboard(), expected(&[seeds::GLOBAL_LEADERBOARD]));
+    }
+
+    #[test]
+    fn multisig_matches_program_seeds() {
+        assert_eq!(multisig(), expected(&[seeds::MULTISIG]));
+    }
+
+    #[test]
+    fn multisig_signer_matches_program_seeds() {
+        assert_eq!(multisig_signer(), expected(&[seeds::MULTISIG_SIGNER]));
+    }
+
+    #[test]
+    fn proposal_matches_program_seeds() {
+        let multisig = Pubkey::new_unique();
+        let index: u64 = 7;
+        assert_eq!(
+            proposal(&multisig, index),
+            expected(&[seeds::PROPOSAL, multisig.as_ref(), &index.to_le_bytes()])
+        );
+    }
+
+    #[test]
+    fn season_matches_program_seeds() {
+        let season_id: u32 = 3;
+        assert_eq!(
+            season(season_id),
+            expected(&[seeds::SEASON, &season_id.to_le_bytes()])
+        );
+    }
+
+    #[test]
+    fn season_pool_matches_program_seeds() {
+        let season = Pubkey::new_unique();
+        assert_eq!(
+            season_pool(&season),
+            expected(&[seeds::SEASON_POOL, season.as_ref()])
+        );
+    }
+
+    #[test]
+    fn season_claim_matches_program_seeds() {
+        let season = Pubkey::new_unique();
+        let player = Pubkey::new_unique();
+        assert_eq!(
+            season_claim(&season, &player),
+            expected(&[seeds::SEASON_CLAIM, season.as_ref(), player.as_ref()])
+        );
+    }
+
+    #[test]
+    fn achievement_matches_program_seeds() {
+        let achievement_id: u32 = 12;
+        assert_eq!(
+            achievement(achievement_id),
+            expected(&[seeds::ACHIEVEMENT, &achievement_id.to_le_bytes()])
+        );
+    }
+
+    #[test]
+    fn badge_matches_program_seeds() {
+        let achievement = Pubkey::new_unique();
+        let player = Pubkey::new_unique();
+        assert_eq!(
+            badge(&achievement, &player),
+            expected(&[seeds::BADGE, achievement.as_ref(), player.as_ref()])
+        );
+    }
+
+    #[test]
+    fn badge_mint_matches_program_seeds() {
+        let achievement = Pubkey::new_unique();
+        let player = Pubkey::new_unique();
+        assert_eq!(
+            badge_mint(&achievement, &player),
+            expected(&[seeds::BADGE_MINT, achievement.as_ref(), player.as_ref()])
+        );
+    }
+
+    #[test]
+    fn challenge_matches_program_seeds() {
+        let challenge_id: u32 = 4;
+        assert_eq!(
+            challenge(challenge_id),
+            expected(&[seeds::CHALLENGE, &challenge_id.to_le_bytes()])
+        );
+    }
+
+    #[test]
+    fn challenge_completion_matches_program_seeds() {
+        let challenge = Pubkey::new_unique();
+        let player = Pubkey::new_unique();
+        let window_index: u64 = 19_000;
+        assert_eq!(
+            challenge_completion(&challenge, &player, window_index),
+            expected(&[
+                seeds::CHALLENGE_COMPLETION,
+                challenge.as_ref(),
+                player.as_ref(),
+                &window_index.to_le_bytes(),
+            ])
+        );
+    }
+}